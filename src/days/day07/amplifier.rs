@@ -0,0 +1,178 @@
+use crate::intcode::{read_intcode, Program, Status};
+
+/// Steps each amplifier may take before the circuit is given up on.
+pub const DEFAULT_BUDGET: u64 = 10_000_000;
+
+/// Lazily yields every ordering of `items` in lexicographic order of their
+/// original positions.
+pub struct Permutations<T> {
+    items: Vec<T>,
+    indices: Vec<usize>,
+    done: bool,
+}
+
+impl<T: Clone> Permutations<T> {
+    pub fn new(items: &[T]) -> Self {
+        Self {
+            items: items.to_vec(),
+            indices: (0..items.len()).collect(),
+            done: false,
+        }
+    }
+
+    fn advance(&mut self) {
+        let n = self.indices.len();
+        let Some(i) = (1..n)
+            .rev()
+            .find(|&i| self.indices[i - 1] < self.indices[i])
+        else {
+            self.done = true;
+            return;
+        };
+        let j = (i..n)
+            .rev()
+            .find(|&j| self.indices[j] > self.indices[i - 1])
+            .unwrap();
+        self.indices.swap(i - 1, j);
+        self.indices[i..].reverse();
+    }
+}
+
+impl<T: Clone> Iterator for Permutations<T> {
+    type Item = Vec<T>;
+
+    fn next(&mut self) -> Option<Vec<T>> {
+        if self.done {
+            return None;
+        }
+        let permutation = self
+            .indices
+            .iter()
+            .map(|&i| self.items[i].clone())
+            .collect();
+        self.advance();
+        Some(permutation)
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Mode {
+    Linear,
+    Feedback,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct BestOrdering {
+    pub phases: Vec<i64>,
+    pub signal: i64,
+}
+
+pub struct AmplifierCircuit {
+    intcode: Vec<i64>,
+    mode: Mode,
+    initial_signal: i64,
+    budget: u64,
+}
+
+impl AmplifierCircuit {
+    pub fn new(intcode: Vec<i64>, mode: Mode) -> Self {
+        Self {
+            intcode,
+            mode,
+            initial_signal: 0,
+            budget: DEFAULT_BUDGET,
+        }
+    }
+
+    pub fn from_file(path: &str, mode: Mode) -> Self {
+        Self::new(read_intcode(path), mode)
+    }
+
+    pub fn with_initial_signal(mut self, signal: i64) -> Self {
+        self.initial_signal = signal;
+        self
+    }
+
+    /// Steps each amplifier may take over the whole run.
+    pub fn with_budget(mut self, steps: u64) -> Self {
+        self.budget = steps;
+        self
+    }
+
+    /// Runs one amplifier per phase setting, chained in order. Returns `None`
+    /// if the chain stalls, halts without producing a signal or an amplifier
+    /// runs out of steps.
+    pub fn run(&self, phases: &[i64]) -> Option<i64> {
+        if phases.is_empty() {
+            return None;
+        }
+        let mut amplifiers = phases
+            .iter()
+            .map(|&phase| Program::new(self.intcode.clone(), vec![phase]).with_budget(self.budget))
+            .collect::<Vec<_>>();
+
+        let mut signal = Some(self.initial_signal);
+        let mut last = None;
+        loop {
+            let mut progressed = false;
+            for amplifier in amplifiers.iter_mut() {
+                if *amplifier.status() == Status::Halted {
+                    signal = None;
+                    continue;
+                }
+                if let Some(value) = signal {
+                    amplifier.get_pending_inputs().push(value);
+                }
+                amplifier.run();
+                if *amplifier.status() == Status::BudgetExhausted {
+                    return None;
+                }
+                signal = amplifier.read_all_outputs().last().copied();
+                progressed |= signal.is_some();
+            }
+            if signal.is_some() {
+                last = signal;
+            }
+            let finished = amplifiers
+                .last()
+                .is_some_and(|a| *a.status() == Status::Halted);
+            if self.mode == Mode::Linear || finished || !progressed {
+                return last;
+            }
+        }
+    }
+
+    pub fn optimize(&self, phases: &[i64]) -> Option<BestOrdering> {
+        self.best_of(Permutations::new(phases))
+    }
+
+    /// Same as `optimize`, but splits the orderings across `threads` workers.
+    pub fn optimize_parallel(&self, phases: &[i64], threads: usize) -> Option<BestOrdering> {
+        let threads = threads.max(1);
+        std::thread::scope(|s| {
+            let workers = (0..threads)
+                .map(|t| {
+                    s.spawn(move || {
+                        self.best_of(Permutations::new(phases).skip(t).step_by(threads))
+                    })
+                })
+                .collect::<Vec<_>>();
+            workers
+                .into_iter()
+                .filter_map(|w| w.join().expect("worker panicked"))
+                .max_by_key(|best| best.signal)
+        })
+    }
+
+    fn best_of(&self, orderings: impl Iterator<Item = Vec<i64>>) -> Option<BestOrdering> {
+        let mut best: Option<BestOrdering> = None;
+        for phases in orderings {
+            if let Some(signal) = self.run(&phases) {
+                if best.as_ref().is_none_or(|b| signal > b.signal) {
+                    best = Some(BestOrdering { phases, signal });
+                }
+            }
+        }
+        best
+    }
+}
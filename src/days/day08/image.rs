@@ -0,0 +1,177 @@
+use std::fmt;
+
+pub const BLACK: u8 = 0;
+pub const WHITE: u8 = 1;
+pub const TRANSPARENT: u8 = 2;
+
+#[derive(Debug, PartialEq)]
+pub enum ImageError {
+    EmptyDimensions,
+    InvalidDigit(char),
+    LengthMismatch { len: usize, layer_size: usize },
+}
+
+impl fmt::Display for ImageError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ImageError::EmptyDimensions => write!(f, "image dimensions must be non-zero"),
+            ImageError::InvalidDigit(c) => write!(f, "invalid pixel digit {:?}", c),
+            ImageError::LengthMismatch { len, layer_size } => write!(
+                f,
+                "data length {} is not a multiple of the layer size {}",
+                len, layer_size
+            ),
+        }
+    }
+}
+
+pub struct SpaceImage {
+    width: usize,
+    height: usize,
+    data: Vec<u8>,
+}
+
+impl SpaceImage {
+    pub fn parse(input: &str, width: usize, height: usize) -> Result<Self, ImageError> {
+        let layer_size = width * height;
+        if layer_size == 0 {
+            return Err(ImageError::EmptyDimensions);
+        }
+        let data = input
+            .trim()
+            .chars()
+            .map(|c| {
+                c.to_digit(10)
+                    .map(|d| d as u8)
+                    .filter(|&d| d <= TRANSPARENT)
+                    .ok_or(ImageError::InvalidDigit(c))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        if data.is_empty() || data.len() % layer_size != 0 {
+            return Err(ImageError::LengthMismatch {
+                len: data.len(),
+                layer_size,
+            });
+        }
+        Ok(Self {
+            width,
+            height,
+            data,
+        })
+    }
+
+    pub fn from_file(path: &str, width: usize, height: usize) -> Result<Self, ImageError> {
+        Self::parse(
+            &std::fs::read_to_string(path).expect("File should be there"),
+            width,
+            height,
+        )
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn layer_count(&self) -> usize {
+        self.data.len() / (self.width * self.height)
+    }
+
+    pub fn layers(&self) -> impl Iterator<Item = &[u8]> {
+        self.data.chunks(self.width * self.height)
+    }
+
+    pub fn layer(&self, idx: usize) -> Option<&[u8]> {
+        self.layers().nth(idx)
+    }
+
+    pub fn histograms(&self) -> Vec<[usize; 10]> {
+        self.layers().map(histogram).collect()
+    }
+
+    /// Product of the 1 and 2 counts on the layer with the fewest 0 digits.
+    pub fn checksum(&self) -> usize {
+        let counts = self
+            .histograms()
+            .into_iter()
+            .min_by_key(|h| h[0])
+            .expect("image has at least one layer");
+        counts[1] * counts[2]
+    }
+
+    /// Stacks the layers front to back; a pixel stays transparent only if it
+    /// is transparent on every layer.
+    pub fn compose(&self) -> Vec<u8> {
+        let mut image = vec![TRANSPARENT; self.width * self.height];
+        for layer in self.layers() {
+            for (pixel, &value) in image.iter_mut().zip(layer) {
+                if *pixel == TRANSPARENT {
+                    *pixel = value;
+                }
+            }
+        }
+        image
+    }
+
+    pub fn render(&self) -> String {
+        self.compose()
+            .chunks(self.width)
+            .map(|row| {
+                row.iter()
+                    .map(|&p| if p == WHITE { '#' } else { ' ' })
+                    .collect::<String>()
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    /// Plain (P1) bitmap where white pixels are ink; transparent is left blank.
+    pub fn to_pbm(&self) -> String {
+        let rows = self
+            .compose()
+            .chunks(self.width)
+            .map(|row| {
+                row.iter()
+                    .map(|&p| if p == WHITE { "1" } else { "0" })
+                    .collect::<Vec<_>>()
+                    .join(" ")
+            })
+            .collect::<Vec<_>>();
+        format!("P1\n{} {}\n{}\n", self.width, self.height, rows.join("\n"))
+    }
+
+    /// Plain (P2) graymap: black is 0, transparent is 1 and white is 2.
+    pub fn to_pgm(&self) -> String {
+        let rows = self
+            .compose()
+            .chunks(self.width)
+            .map(|row| {
+                row.iter()
+                    .map(|&p| match p {
+                        BLACK => "0",
+                        WHITE => "2",
+                        _ => "1",
+                    })
+                    .collect::<Vec<_>>()
+                    .join(" ")
+            })
+            .collect::<Vec<_>>();
+        format!(
+            "P2\n{} {}\n2\n{}\n",
+            self.width,
+            self.height,
+            rows.join("\n")
+        )
+    }
+}
+
+pub fn histogram(layer: &[u8]) -> [usize; 10] {
+    let mut counts = [0; 10];
+    for &d in layer {
+        counts[d as usize] += 1;
+    }
+    counts
+}
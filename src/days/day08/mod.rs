@@ -1,10 +1,11 @@
 #![allow(dead_code)]
+mod image;
 mod part1;
 mod part2;
 
 pub fn run() {
     println!("====================Day 08====================");
     println!("Part 1: {}", part1::part1("inputs/day08"));
-    println!("Part 2: {:#?}", part2::part2("inputs/day08"));
+    println!("Part 2:\n{}", part2::part2("inputs/day08"));
     println!("==============================================");
 }
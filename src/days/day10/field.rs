@@ -0,0 +1,163 @@
+use std::cmp::Ordering;
+use std::collections::{BTreeMap, HashMap, HashSet, VecDeque};
+
+/// A reduced `(dr, dc)` step from a station. Directions are ordered clockwise
+/// starting from straight up, compared exactly with cross products.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct Direction {
+    pub dr: i32,
+    pub dc: i32,
+}
+
+impl Direction {
+    fn half(&self) -> u8 {
+        if self.dc > 0 || (self.dc == 0 && self.dr < 0) {
+            0
+        } else {
+            1
+        }
+    }
+}
+
+impl Ord for Direction {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.half().cmp(&other.half()).then_with(|| {
+            let cross = self.dc as i64 * -other.dr as i64 - -self.dr as i64 * other.dc as i64;
+            cross.cmp(&0)
+        })
+    }
+}
+
+impl PartialOrd for Direction {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+pub type Position = (usize, usize);
+
+pub struct AsteroidField {
+    asteroids: Vec<Position>,
+}
+
+impl AsteroidField {
+    pub fn parse(input: &str) -> Self {
+        let asteroids = input
+            .lines()
+            .enumerate()
+            .flat_map(|(row, line)| {
+                line.chars()
+                    .enumerate()
+                    .filter(|&(_, c)| c == '#')
+                    .map(move |(col, _)| (row, col))
+            })
+            .collect::<Vec<_>>();
+        Self { asteroids }
+    }
+
+    pub fn from_file(path: &str) -> Self {
+        Self::parse(&std::fs::read_to_string(path).expect("File should be there"))
+    }
+
+    pub fn asteroids(&self) -> &[Position] {
+        &self.asteroids
+    }
+
+    pub fn visible_from(&self, station: Position) -> usize {
+        self.asteroids
+            .iter()
+            .filter(|&&a| a != station)
+            .map(|&a| direction(station, a).1)
+            .collect::<HashSet<_>>()
+            .len()
+    }
+
+    pub fn visibility_counts(&self) -> HashMap<Position, usize> {
+        self.asteroids
+            .iter()
+            .map(|&a| (a, self.visible_from(a)))
+            .collect()
+    }
+
+    /// The asteroid that sees the most others, with its count. Ties go to the
+    /// first asteroid in reading order.
+    pub fn best_station(&self) -> Option<(Position, usize)> {
+        self.asteroids
+            .iter()
+            .map(|&a| (a, self.visible_from(a)))
+            .fold(None, |best, (a, count)| match best {
+                Some((_, c)) if c >= count => best,
+                _ => Some((a, count)),
+            })
+    }
+
+    pub fn vaporization_order(&self, station: Position) -> Vaporization {
+        let mut rays: BTreeMap<Direction, Vec<(i32, Position)>> = BTreeMap::new();
+        for &asteroid in self.asteroids.iter().filter(|&&a| a != station) {
+            let (distance, dir) = direction(station, asteroid);
+            rays.entry(dir).or_default().push((distance, asteroid));
+        }
+        let rays = rays
+            .into_values()
+            .map(|mut ray| {
+                ray.sort_by_key(|&(distance, _)| distance);
+                ray.into_iter().map(|(_, a)| a).collect::<VecDeque<_>>()
+            })
+            .collect::<Vec<_>>();
+        Vaporization {
+            remaining: rays.iter().map(|r| r.len()).sum(),
+            rays,
+            cursor: 0,
+        }
+    }
+}
+
+/// Asteroids in the order a clockwise-rotating laser destroys them.
+pub struct Vaporization {
+    rays: Vec<VecDeque<Position>>,
+    cursor: usize,
+    remaining: usize,
+}
+
+impl Iterator for Vaporization {
+    type Item = Position;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.remaining == 0 {
+            return None;
+        }
+        loop {
+            let ray = self.cursor;
+            self.cursor = (self.cursor + 1) % self.rays.len();
+            if let Some(asteroid) = self.rays[ray].pop_front() {
+                self.remaining -= 1;
+                return Some(asteroid);
+            }
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining, Some(self.remaining))
+    }
+}
+
+fn direction(from: Position, to: Position) -> (i32, Direction) {
+    let dr = to.0 as i32 - from.0 as i32;
+    let dc = to.1 as i32 - from.1 as i32;
+    let g = gcd(dr.abs(), dc.abs());
+    (
+        g,
+        Direction {
+            dr: dr / g,
+            dc: dc / g,
+        },
+    )
+}
+
+fn gcd(a: i32, b: i32) -> i32 {
+    if b == 0 {
+        a
+    } else {
+        gcd(b, a % b)
+    }
+}
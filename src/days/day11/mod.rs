@@ -1,10 +1,11 @@
 #![allow(dead_code)]
 mod part1;
 mod part2;
+mod robot;
 
 pub fn run() {
     println!("====================Day 11====================");
     println!("Part 1: {}", part1::part1("inputs/day11"));
-    println!("Part 2: {}", part2::part2("inputs/day11"));
+    println!("Part 2:\n{}", part2::part2("inputs/day11"));
     println!("==============================================");
 }
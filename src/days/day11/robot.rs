@@ -0,0 +1,268 @@
+use std::collections::{HashMap, HashSet, VecDeque};
+
+use crate::intcode::{read_intcode, Program, Status};
+
+/// Steps the painting program may take over a whole run.
+pub const DEFAULT_BUDGET: u64 = 100_000_000;
+
+const DIRECTIONS: [(i32, i32); 4] = [(-1, 0), (0, 1), (1, 0), (0, -1)];
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Color {
+    Black,
+    White,
+}
+
+impl Color {
+    pub fn from_int(value: i64) -> Self {
+        match value {
+            0 => Color::Black,
+            1 => Color::White,
+            _ => panic!("invalid color"),
+        }
+    }
+
+    pub fn to_int(self) -> i64 {
+        match self {
+            Color::Black => 0,
+            Color::White => 1,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Turn {
+    Left,
+    Right,
+}
+
+impl Turn {
+    pub fn from_int(value: i64) -> Self {
+        match value {
+            0 => Turn::Left,
+            1 => Turn::Right,
+            _ => panic!("invalid turn"),
+        }
+    }
+}
+
+/// Decides what to paint and where to turn given the colour under the robot.
+/// Returning `None` stops the robot.
+pub trait Brain {
+    fn decide(&mut self, color: Color) -> Option<(Color, Turn)>;
+}
+
+pub struct IntcodeBrain {
+    program: Program,
+}
+
+impl IntcodeBrain {
+    pub fn new(intcode: Vec<i64>) -> Self {
+        Self {
+            program: Program::new(intcode, vec![]).with_budget(DEFAULT_BUDGET),
+        }
+    }
+
+    pub fn from_file(path: &str) -> Self {
+        Self::new(read_intcode(path))
+    }
+
+    pub fn with_budget(mut self, steps: u64) -> Self {
+        self.program.set_budget(Some(steps));
+        self
+    }
+}
+
+impl Brain for IntcodeBrain {
+    fn decide(&mut self, color: Color) -> Option<(Color, Turn)> {
+        self.program.get_pending_inputs().push(color.to_int());
+        self.program.run();
+        if self.program.status() == &Status::BudgetExhausted {
+            panic!("Painting program did not finish within its step budget");
+        }
+        match self.program.read_all_outputs()[..] {
+            [paint, turn] => Some((Color::from_int(paint), Turn::from_int(turn))),
+            [] => None,
+            ref outputs => panic!("Painting program answered {:?}", outputs),
+        }
+    }
+}
+
+/// Replays a fixed list of decisions, ignoring the colours it is shown.
+pub struct ScriptedBrain {
+    moves: VecDeque<(Color, Turn)>,
+}
+
+impl ScriptedBrain {
+    pub fn new(moves: Vec<(Color, Turn)>) -> Self {
+        Self {
+            moves: moves.into(),
+        }
+    }
+}
+
+impl Brain for ScriptedBrain {
+    fn decide(&mut self, _color: Color) -> Option<(Color, Turn)> {
+        self.moves.pop_front()
+    }
+}
+
+#[derive(Clone, Default)]
+pub struct Hull {
+    panels: HashMap<(i32, i32), Color>,
+}
+
+impl Hull {
+    pub fn get(&self, pos: (i32, i32)) -> Color {
+        *self.panels.get(&pos).unwrap_or(&Color::Black)
+    }
+
+    pub fn paint(&mut self, pos: (i32, i32), color: Color) {
+        self.panels.insert(pos, color);
+    }
+
+    /// Inclusive `(top_left, bottom_right)` corners of the white panels.
+    pub fn bounds(&self) -> Option<((i32, i32), (i32, i32))> {
+        let mut white = self
+            .panels
+            .iter()
+            .filter(|(_, &c)| c == Color::White)
+            .map(|(&pos, _)| pos);
+        let first = white.next()?;
+        Some(white.fold((first, first), |(lo, hi), (r, c)| {
+            ((lo.0.min(r), lo.1.min(c)), (hi.0.max(r), hi.1.max(c)))
+        }))
+    }
+
+    pub fn render(&self) -> String {
+        let Some((lo, hi)) = self.bounds() else {
+            return String::new();
+        };
+        (lo.0..=hi.0)
+            .map(|r| {
+                (lo.1..=hi.1)
+                    .map(|c| match self.get((r, c)) {
+                        Color::White => '#',
+                        Color::Black => ' ',
+                    })
+                    .collect::<String>()
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct PaintEvent {
+    pub position: (i32, i32),
+    pub color: Color,
+    pub turn: Turn,
+}
+
+pub struct HullRobot<B: Brain> {
+    brain: B,
+    hull: Hull,
+    start: Hull,
+    position: (i32, i32),
+    heading: usize,
+    painted: HashSet<(i32, i32)>,
+    history: Vec<PaintEvent>,
+}
+
+impl<B: Brain> HullRobot<B> {
+    pub fn new(brain: B, start_color: Color) -> Self {
+        let mut hull = Hull::default();
+        hull.paint((0, 0), start_color);
+        Self {
+            brain,
+            start: hull.clone(),
+            hull,
+            position: (0, 0),
+            heading: 0,
+            painted: HashSet::new(),
+            history: vec![],
+        }
+    }
+
+    pub fn step(&mut self) -> Option<PaintEvent> {
+        let (color, turn) = self.brain.decide(self.hull.get(self.position))?;
+        let event = PaintEvent {
+            position: self.position,
+            color,
+            turn,
+        };
+        self.hull.paint(self.position, color);
+        self.painted.insert(self.position);
+        self.history.push(event);
+
+        self.heading = match turn {
+            Turn::Left => (self.heading + 3) % 4,
+            Turn::Right => (self.heading + 1) % 4,
+        };
+        let dir = DIRECTIONS[self.heading];
+        self.position = (self.position.0 + dir.0, self.position.1 + dir.1);
+        Some(event)
+    }
+
+    pub fn run(&mut self) -> &Hull {
+        while self.step().is_some() {}
+        &self.hull
+    }
+
+    pub fn hull(&self) -> &Hull {
+        &self.hull
+    }
+
+    pub fn painted_count(&self) -> usize {
+        self.painted.len()
+    }
+
+    pub fn history(&self) -> &[PaintEvent] {
+        &self.history
+    }
+
+    pub fn replay(&self) -> Replay<'_> {
+        Replay {
+            hull: self.start.clone(),
+            events: self.history.iter(),
+        }
+    }
+}
+
+/// Rebuilds the hull one paint event at a time, yielding each frame.
+pub struct Replay<'a> {
+    hull: Hull,
+    events: std::slice::Iter<'a, PaintEvent>,
+}
+
+impl Iterator for Replay<'_> {
+    type Item = (PaintEvent, Hull);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let event = *self.events.next()?;
+        self.hull.paint(event.position, event.color);
+        Some((event, self.hull.clone()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn example() {
+        let moves = [(1, 0), (0, 0), (1, 0), (1, 0), (0, 1), (1, 0), (1, 0)]
+            .into_iter()
+            .map(|(paint, turn)| (Color::from_int(paint), Turn::from_int(turn)))
+            .collect();
+        let mut robot = HullRobot::new(ScriptedBrain::new(moves), Color::Black);
+        assert_eq!(robot.run().render(), "  #\n  #\n## ");
+        assert_eq!(robot.painted_count(), 6);
+
+        let frames = robot.replay().collect::<Vec<_>>();
+        assert_eq!(frames.len(), 7);
+        assert_eq!(frames[0].0.position, (0, 0));
+        assert_eq!(frames[0].1.render(), "#");
+        assert_eq!(frames[6].1.render(), robot.hull().render());
+    }
+}
@@ -1,4 +1,5 @@
 #![allow(dead_code)]
+mod nbody;
 mod part1;
 mod part2;
 
@@ -0,0 +1,155 @@
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Body<const D: usize> {
+    pub position: [i64; D],
+    pub velocity: [i64; D],
+}
+
+impl<const D: usize> Body<D> {
+    pub fn energy(&self) -> i64 {
+        let potential = self.position.iter().map(|p| p.abs()).sum::<i64>();
+        let kinetic = self.velocity.iter().map(|v| v.abs()).sum::<i64>();
+        potential * kinetic
+    }
+}
+
+/// Bodies pulling on each other one unit per axis per step, in `D` dimensions.
+#[derive(Clone, Debug)]
+pub struct NBody<const D: usize> {
+    bodies: Vec<Body<D>>,
+}
+
+impl<const D: usize> NBody<D> {
+    pub fn new(positions: Vec<[i64; D]>) -> Self {
+        let bodies = positions
+            .into_iter()
+            .map(|position| Body {
+                position,
+                velocity: [0; D],
+            })
+            .collect();
+        Self { bodies }
+    }
+
+    /// Parses one `<x=.., y=.., z=..>` line per body; each line must hold
+    /// exactly `D` coordinates.
+    pub fn parse(input: &str) -> Self {
+        let positions = input
+            .lines()
+            .filter(|l| !l.trim().is_empty())
+            .map(|line| {
+                let coords = line
+                    .trim()
+                    .strip_prefix('<')
+                    .and_then(|l| l.strip_suffix('>'))
+                    .expect("body should be wrapped in <>")
+                    .split(',')
+                    .map(|coord| {
+                        let (_, val) = coord.split_once('=').expect("coordinate should be k=v");
+                        val.trim().parse::<i64>().expect("failed to parse int")
+                    })
+                    .collect::<Vec<_>>();
+                <[i64; D]>::try_from(coords).expect("wrong number of coordinates")
+            })
+            .collect();
+        Self::new(positions)
+    }
+
+    pub fn from_file(path: &str) -> Self {
+        Self::parse(&std::fs::read_to_string(path).expect("File should be there"))
+    }
+
+    pub fn bodies(&self) -> &[Body<D>] {
+        &self.bodies
+    }
+
+    pub fn step(&mut self) {
+        for axis in 0..D {
+            for i in 0..self.bodies.len() {
+                let pos = self.bodies[i].position[axis];
+                let pull = self
+                    .bodies
+                    .iter()
+                    .map(|b| (b.position[axis] - pos).signum())
+                    .sum::<i64>();
+                self.bodies[i].velocity[axis] += pull;
+            }
+        }
+        for body in self.bodies.iter_mut() {
+            for axis in 0..D {
+                body.position[axis] += body.velocity[axis];
+            }
+        }
+    }
+
+    pub fn run(&mut self, steps: usize) {
+        for _ in 0..steps {
+            self.step();
+        }
+    }
+
+    pub fn total_energy(&self) -> i64 {
+        self.bodies.iter().map(|b| b.energy()).sum()
+    }
+
+    pub fn energy_after(&self, steps: usize) -> i64 {
+        let mut system = self.clone();
+        system.run(steps);
+        system.total_energy()
+    }
+
+    /// Steps until the given axis returns to its current state. Each step is
+    /// reversible, so the first repeat is always the starting state.
+    pub fn axis_period(&self, axis: usize) -> u64 {
+        let initial = self
+            .bodies
+            .iter()
+            .map(|b| (b.position[axis], b.velocity[axis]))
+            .collect::<Vec<_>>();
+        let mut state = initial.clone();
+        let mut period = 0;
+        loop {
+            let positions = state.iter().map(|&(p, _)| p).collect::<Vec<_>>();
+            for (p, v) in state.iter_mut() {
+                *v += positions.iter().map(|o| (o - *p).signum()).sum::<i64>();
+            }
+            for (p, v) in state.iter_mut() {
+                *p += *v;
+            }
+            period += 1;
+            if state == initial {
+                return period;
+            }
+        }
+    }
+
+    pub fn periods(&self) -> [u64; D] {
+        std::array::from_fn(|axis| self.axis_period(axis))
+    }
+
+    /// Steps until the whole system repeats, or `None` if that overflows `u128`.
+    pub fn cycle_length(&self) -> Option<u128> {
+        self.periods()
+            .iter()
+            .try_fold(1u128, |acc, &p| lcm(acc, p as u128))
+    }
+}
+
+fn lcm(a: u128, b: u128) -> Option<u128> {
+    if a == 0 || b == 0 {
+        return Some(0);
+    }
+    (a / gcd(a, b)).checked_mul(b)
+}
+
+fn gcd(a: u128, b: u128) -> u128 {
+    let mut a = a;
+    let mut b = b;
+
+    while b != 0 {
+        let temp = b;
+        b = a % b;
+        a = temp;
+    }
+
+    a
+}
@@ -0,0 +1,334 @@
+use std::fmt;
+use std::io::{BufRead, Write};
+
+use crate::intcode::patch::Patch;
+use crate::intcode::program::Observer;
+use crate::intcode::{read_intcode, Program, Status};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Tile {
+    Empty,
+    Wall,
+    Block,
+    Paddle,
+    Ball,
+}
+
+impl Tile {
+    pub fn from_int(id: i64) -> Self {
+        Self::try_from_int(id).unwrap_or_else(|| panic!("invalid tile id {}", id))
+    }
+
+    pub fn try_from_int(id: i64) -> Option<Self> {
+        match id {
+            0 => Some(Tile::Empty),
+            1 => Some(Tile::Wall),
+            2 => Some(Tile::Block),
+            3 => Some(Tile::Paddle),
+            4 => Some(Tile::Ball),
+            _ => None,
+        }
+    }
+
+    pub fn glyph(self) -> char {
+        match self {
+            Tile::Empty => ' ',
+            Tile::Wall => '#',
+            Tile::Block => '=',
+            Tile::Paddle => '_',
+            Tile::Ball => 'o',
+        }
+    }
+}
+
+/// Output the screen could not make sense of.
+#[derive(Debug, PartialEq)]
+pub enum ScreenError {
+    NegativePosition {
+        x: i64,
+        y: i64,
+    },
+    InvalidTile(i64),
+    /// The program stopped partway through a triple.
+    PartialTriple(Vec<i64>),
+}
+
+impl fmt::Display for ScreenError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ScreenError::NegativePosition { x, y } => {
+                write!(f, "cannot draw at negative position ({}, {})", x, y)
+            }
+            ScreenError::InvalidTile(id) => write!(f, "invalid tile id {}", id),
+            ScreenError::PartialTriple(values) => {
+                write!(f, "output ended partway through a triple: {:?}", values)
+            }
+        }
+    }
+}
+
+/// Everything the game has drawn so far. The screen grows to fit whatever
+/// positions the tile stream uses.
+#[derive(Clone, Debug, Default)]
+pub struct Screen {
+    tiles: Vec<Vec<Tile>>,
+    score: i64,
+}
+
+impl Screen {
+    pub fn draw(&mut self, x: usize, y: usize, tile: Tile) {
+        if self.tiles.len() <= y {
+            self.tiles.resize(y + 1, vec![]);
+        }
+        let row = &mut self.tiles[y];
+        if row.len() <= x {
+            row.resize(x + 1, Tile::Empty);
+        }
+        row[x] = tile;
+    }
+
+    pub fn get(&self, x: usize, y: usize) -> Tile {
+        self.tiles
+            .get(y)
+            .and_then(|row| row.get(x))
+            .copied()
+            .unwrap_or(Tile::Empty)
+    }
+
+    pub fn width(&self) -> usize {
+        self.tiles.iter().map(|row| row.len()).max().unwrap_or(0)
+    }
+
+    pub fn height(&self) -> usize {
+        self.tiles.len()
+    }
+
+    pub fn score(&self) -> i64 {
+        self.score
+    }
+
+    pub fn count(&self, tile: Tile) -> usize {
+        self.tiles.iter().flatten().filter(|&&t| t == tile).count()
+    }
+
+    /// Position of the first `tile` on screen, scanning row by row.
+    pub fn find(&self, tile: Tile) -> Option<(usize, usize)> {
+        self.tiles
+            .iter()
+            .enumerate()
+            .find_map(|(y, row)| row.iter().position(|&t| t == tile).map(|x| (x, y)))
+    }
+
+    pub fn render(&self) -> String {
+        let width = self.width();
+        let mut frame = format!("Score: {}\n", self.score);
+        for row in &self.tiles {
+            let line = (0..width)
+                .map(|x| row.get(x).copied().unwrap_or(Tile::Empty).glyph())
+                .collect::<String>();
+            frame += line.trim_end();
+            frame.push('\n');
+        }
+        frame
+    }
+
+    /// Applies `(x, y, id)` output triples, where `(-1, 0, n)` sets the
+    /// score. Triples before a bad one are still drawn.
+    fn apply(&mut self, outputs: &[i64]) -> Result<(), ScreenError> {
+        for triple in outputs.chunks(3) {
+            match *triple {
+                [-1, 0, score] => self.score = score,
+                [x, y, id] => {
+                    let (Ok(col), Ok(row)) = (usize::try_from(x), usize::try_from(y)) else {
+                        return Err(ScreenError::NegativePosition { x, y });
+                    };
+                    let tile = Tile::try_from_int(id).ok_or(ScreenError::InvalidTile(id))?;
+                    self.draw(col, row, tile);
+                }
+                _ => return Err(ScreenError::PartialTriple(triple.to_vec())),
+            }
+        }
+        Ok(())
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Tilt {
+    Left,
+    Neutral,
+    Right,
+}
+
+impl Tilt {
+    pub fn to_int(self) -> i64 {
+        match self {
+            Tilt::Left => -1,
+            Tilt::Neutral => 0,
+            Tilt::Right => 1,
+        }
+    }
+}
+
+/// Whatever is holding the joystick, asked once per frame.
+pub trait Joystick {
+    fn tilt(&mut self, screen: &Screen) -> Tilt;
+}
+
+/// Keeps the paddle under where the ball is going next rather than where
+/// it is now, so it is already in place when the ball arrives.
+#[derive(Default)]
+pub struct Autopilot {
+    last_ball: Option<(usize, usize)>,
+}
+
+impl Autopilot {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl Joystick for Autopilot {
+    fn tilt(&mut self, screen: &Screen) -> Tilt {
+        let (Some(ball), Some(paddle)) = (screen.find(Tile::Ball), screen.find(Tile::Paddle))
+        else {
+            return Tilt::Neutral;
+        };
+        let dx = self
+            .last_ball
+            .map_or(0, |last| ball.0 as i64 - last.0 as i64);
+        self.last_ball = Some(ball);
+        let target = ball.0 as i64 + dx;
+        match target.cmp(&(paddle.0 as i64)) {
+            std::cmp::Ordering::Less => Tilt::Left,
+            std::cmp::Ordering::Equal => Tilt::Neutral,
+            std::cmp::Ordering::Greater => Tilt::Right,
+        }
+    }
+}
+
+/// A player at the keyboard: `a` or `h` tilts left, `d` or `l` tilts right
+/// and anything else, including an empty line, leaves the joystick alone.
+pub struct Keyboard<R: BufRead> {
+    input: R,
+}
+
+impl<R: BufRead> Keyboard<R> {
+    pub fn new(input: R) -> Self {
+        Self { input }
+    }
+}
+
+impl<R: BufRead> Joystick for Keyboard<R> {
+    fn tilt(&mut self, _screen: &Screen) -> Tilt {
+        let mut line = String::new();
+        if self.input.read_line(&mut line).is_err() {
+            return Tilt::Neutral;
+        }
+        match line.trim() {
+            "a" | "h" | "left" => Tilt::Left,
+            "d" | "l" | "right" => Tilt::Right,
+            _ => Tilt::Neutral,
+        }
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub struct GameReport {
+    pub score: i64,
+    /// Number of times the game stopped to read the joystick.
+    pub frames: usize,
+    pub blocks_left: usize,
+}
+
+pub struct Cabinet {
+    intcode: Vec<i64>,
+    patches: Vec<Patch>,
+}
+
+impl Cabinet {
+    pub fn new(intcode: Vec<i64>) -> Self {
+        Self {
+            intcode,
+            patches: vec![],
+        }
+    }
+
+    pub fn from_file(path: &str) -> Self {
+        Self::new(read_intcode(path))
+    }
+
+    /// Applies `patch` to the game every time it is switched on.
+    pub fn with_patch(mut self, patch: Patch) -> Self {
+        self.patches.push(patch);
+        self
+    }
+
+    /// Sets the cabinet to play without quarters.
+    pub fn free_play(self) -> Self {
+        self.with_patch(Patch::set("free play", 0, 2))
+    }
+
+    fn power_on(&self) -> Program {
+        let mut program = Program::new(self.intcode.clone(), vec![]);
+        for patch in &self.patches {
+            program.patch(patch);
+        }
+        program
+    }
+
+    /// Screen as drawn before the game first asks for input.
+    pub fn boot(&self) -> Result<Screen, ScreenError> {
+        let mut program = self.power_on();
+        program.run();
+        let mut screen = Screen::default();
+        screen.apply(&program.read_all_outputs())?;
+        Ok(screen)
+    }
+
+    /// Plays to the end without drawing anything.
+    pub fn play(&self, joystick: &mut impl Joystick) -> Result<GameReport, ScreenError> {
+        self.play_with(joystick, &mut (), |_| {})
+    }
+
+    /// Plays to the end, drawing every frame to `out`.
+    pub fn play_visible(
+        &self,
+        joystick: &mut impl Joystick,
+        mut out: impl Write,
+    ) -> Result<GameReport, ScreenError> {
+        self.play_with(joystick, &mut (), |screen| {
+            let _ = write!(out, "{}", screen.render());
+            let _ = out.flush();
+        })
+    }
+
+    /// Plays to the end, showing every executed instruction to `observer`
+    /// and calling `on_frame` with the screen every time the game waits for
+    /// the joystick.
+    pub fn play_with(
+        &self,
+        joystick: &mut impl Joystick,
+        observer: &mut impl Observer,
+        mut on_frame: impl FnMut(&Screen),
+    ) -> Result<GameReport, ScreenError> {
+        let mut program = self.power_on();
+        let mut screen = Screen::default();
+        let mut frames = 0;
+        loop {
+            program.run_observed(observer);
+            screen.apply(&program.read_all_outputs())?;
+            if program.status() != &Status::WaitingForInput {
+                break;
+            }
+            frames += 1;
+            on_frame(&screen);
+            let tilt = joystick.tilt(&screen);
+            program.get_pending_inputs().push(tilt.to_int());
+        }
+        Ok(GameReport {
+            score: screen.score,
+            frames,
+            blocks_left: screen.count(Tile::Block),
+        })
+    }
+}
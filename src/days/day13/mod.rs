@@ -1,4 +1,5 @@
 #![allow(dead_code)]
+mod cabinet;
 mod part1;
 mod part2;
 
@@ -8,3 +9,17 @@ pub fn run() {
     println!("Part 2: {}", part2::part2("inputs/day13"));
     println!("==============================================");
 }
+
+/// Plays the arcade game by hand instead of leaving it to the autopilot.
+pub fn play() {
+    let report = part2::play("inputs/day13");
+    println!("Score: {}", report.score);
+    println!("Blocks left: {}", report.blocks_left);
+}
+
+/// Profiles a full autopilot game and prints where in memory it ran.
+pub fn profile() {
+    let (report, coverage) = part2::profile("inputs/day13");
+    println!("Score: {}", report.score);
+    print!("{}", coverage.summary(coverage.extent()));
+}
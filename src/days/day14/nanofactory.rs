@@ -0,0 +1,222 @@
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::fmt;
+
+#[derive(Debug, PartialEq)]
+pub enum ReactionError {
+    Parse(String),
+    DuplicateProducer(String),
+    Cycle(Vec<String>),
+    Unproducible(String),
+    NotConsumed { product: String, raw: String },
+}
+
+impl fmt::Display for ReactionError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ReactionError::Parse(line) => write!(f, "could not parse reaction {:?}", line),
+            ReactionError::DuplicateProducer(c) => {
+                write!(f, "{} is produced by more than one reaction", c)
+            }
+            ReactionError::Cycle(chems) => {
+                write!(f, "reactions form a cycle through {}", chems.join(", "))
+            }
+            ReactionError::Unproducible(c) => {
+                write!(f, "{} is neither a raw material nor produced", c)
+            }
+            ReactionError::NotConsumed { product, raw } => {
+                write!(f, "producing {} does not consume {}", product, raw)
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+struct Reaction {
+    ins: Vec<(String, u64)>,
+    out: u64,
+}
+
+#[derive(Debug, Default, PartialEq)]
+pub struct BillOfMaterials {
+    /// Total amount of each chemical consumed, including the target itself.
+    pub required: HashMap<String, u64>,
+    /// How many times each reaction has to run.
+    pub reactions: HashMap<String, u64>,
+    /// Surplus left over once the target has been made.
+    pub leftovers: HashMap<String, u64>,
+}
+
+impl BillOfMaterials {
+    pub fn amount(&self, chemical: &str) -> u64 {
+        *self.required.get(chemical).unwrap_or(&0)
+    }
+}
+
+pub struct Nanofactory {
+    reactions: HashMap<String, Reaction>,
+    raw: HashSet<String>,
+    order: Vec<String>,
+}
+
+impl Nanofactory {
+    pub fn parse(input: &str) -> Result<Self, ReactionError> {
+        Self::parse_with_raw(input, &["ORE"])
+    }
+
+    pub fn parse_with_raw(input: &str, raw: &[&str]) -> Result<Self, ReactionError> {
+        let mut reactions = HashMap::new();
+        for line in input.lines().filter(|l| !l.trim().is_empty()) {
+            let err = || ReactionError::Parse(line.to_string());
+            let (ins, out) = line.split_once("=>").ok_or_else(err)?;
+            let (out_name, out_qty) = parse_pair(out).ok_or_else(err)?;
+            let ins = ins
+                .split(',')
+                .map(|pair| parse_pair(pair).ok_or_else(err))
+                .collect::<Result<Vec<_>, _>>()?;
+            let reaction = Reaction { ins, out: out_qty };
+            if reactions.insert(out_name.clone(), reaction).is_some() {
+                return Err(ReactionError::DuplicateProducer(out_name));
+            }
+        }
+
+        let raw = raw.iter().map(|r| r.to_string()).collect::<HashSet<_>>();
+        for reaction in reactions.values() {
+            for (name, _) in &reaction.ins {
+                if !reactions.contains_key(name) && !raw.contains(name) {
+                    return Err(ReactionError::Unproducible(name.clone()));
+                }
+            }
+        }
+
+        let order = topological_order(&reactions, &raw)?;
+        Ok(Self {
+            reactions,
+            raw,
+            order,
+        })
+    }
+
+    pub fn from_file(path: &str) -> Result<Self, ReactionError> {
+        Self::parse(&std::fs::read_to_string(path).expect("File should be there"))
+    }
+
+    /// Chemicals ordered so that every product comes before its inputs.
+    pub fn order(&self) -> &[String] {
+        &self.order
+    }
+
+    pub fn bill_of_materials(
+        &self,
+        target: &str,
+        quantity: u64,
+    ) -> Result<BillOfMaterials, ReactionError> {
+        if !self.reactions.contains_key(target) && !self.raw.contains(target) {
+            return Err(ReactionError::Unproducible(target.to_string()));
+        }
+        let mut bill = BillOfMaterials::default();
+        bill.required.insert(target.to_string(), quantity);
+
+        for chemical in &self.order {
+            let Some(&needed) = bill.required.get(chemical) else {
+                continue;
+            };
+            let Some(reaction) = self.reactions.get(chemical) else {
+                continue;
+            };
+            let runs = needed.div_ceil(reaction.out);
+            if runs == 0 {
+                continue;
+            }
+            bill.reactions.insert(chemical.clone(), runs);
+            let surplus = runs * reaction.out - needed;
+            if surplus > 0 {
+                bill.leftovers.insert(chemical.clone(), surplus);
+            }
+            for (name, qty) in &reaction.ins {
+                *bill.required.entry(name.clone()).or_insert(0) += qty * runs;
+            }
+        }
+        Ok(bill)
+    }
+
+    pub fn raw_cost(&self, target: &str, quantity: u64, raw: &str) -> Result<u64, ReactionError> {
+        Ok(self.bill_of_materials(target, quantity)?.amount(raw))
+    }
+
+    /// Largest amount of `product` that can be made from `budget` units of
+    /// `raw`, assuming every other raw material is unlimited.
+    pub fn max_output(&self, product: &str, raw: &str, budget: u64) -> Result<u64, ReactionError> {
+        if self.raw_cost(product, 1, raw)? == 0 {
+            return Err(ReactionError::NotConsumed {
+                product: product.to_string(),
+                raw: raw.to_string(),
+            });
+        }
+
+        let mut lo = 0;
+        let mut hi = 1;
+        while self.raw_cost(product, hi, raw)? <= budget {
+            lo = hi;
+            hi *= 2;
+        }
+        while hi - lo > 1 {
+            let mid = lo + (hi - lo) / 2;
+            if self.raw_cost(product, mid, raw)? <= budget {
+                lo = mid;
+            } else {
+                hi = mid;
+            }
+        }
+        Ok(lo)
+    }
+}
+
+fn parse_pair(pair: &str) -> Option<(String, u64)> {
+    let (qty, name) = pair.trim().split_once(' ')?;
+    Some((name.trim().to_string(), qty.parse::<u64>().ok()?))
+}
+
+fn topological_order(
+    reactions: &HashMap<String, Reaction>,
+    raw: &HashSet<String>,
+) -> Result<Vec<String>, ReactionError> {
+    let mut consumers: HashMap<&str, usize> = HashMap::new();
+    for name in reactions.keys().chain(raw.iter()) {
+        consumers.entry(name).or_insert(0);
+    }
+    for reaction in reactions.values() {
+        for (name, _) in &reaction.ins {
+            *consumers.entry(name).or_insert(0) += 1;
+        }
+    }
+
+    let mut q = consumers
+        .iter()
+        .filter(|(_, &n)| n == 0)
+        .map(|(&name, _)| name)
+        .collect::<VecDeque<_>>();
+    let mut order = vec![];
+    while let Some(name) = q.pop_front() {
+        order.push(name.to_string());
+        if let Some(reaction) = reactions.get(name) {
+            for (input, _) in &reaction.ins {
+                let n = consumers.get_mut(input.as_str()).unwrap();
+                *n -= 1;
+                if *n == 0 {
+                    q.push_back(input);
+                }
+            }
+        }
+    }
+
+    if order.len() != consumers.len() {
+        let mut stuck = consumers
+            .into_iter()
+            .filter(|(_, n)| *n > 0)
+            .map(|(name, _)| name.to_string())
+            .collect::<Vec<_>>();
+        stuck.sort();
+        return Err(ReactionError::Cycle(stuck));
+    }
+    Ok(order)
+}
@@ -0,0 +1,180 @@
+use std::collections::{HashMap, VecDeque};
+
+use crate::intcode::{read_intcode, Program, Status};
+
+/// Steps the droid's program may take over a whole exploration.
+pub const DEFAULT_BUDGET: u64 = 100_000_000;
+
+const MOVES: [u8; 4] = [1, 2, 3, 4];
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Tile {
+    Wall,
+    Open,
+    Oxygen,
+}
+
+impl Tile {
+    pub fn from_int(status: i64) -> Self {
+        match status {
+            0 => Tile::Wall,
+            1 => Tile::Open,
+            2 => Tile::Oxygen,
+            _ => panic!("invalid status code"),
+        }
+    }
+}
+
+pub fn step(pos: (i32, i32), dir: u8) -> (i32, i32) {
+    match dir {
+        1 => (pos.0, pos.1 - 1),
+        2 => (pos.0, pos.1 + 1),
+        3 => (pos.0 - 1, pos.1),
+        4 => (pos.0 + 1, pos.1),
+        _ => unreachable!("invalid direction"),
+    }
+}
+
+pub fn opposite(dir: u8) -> u8 {
+    match dir {
+        1 => 2,
+        2 => 1,
+        3 => 4,
+        4 => 3,
+        _ => unreachable!("invalid direction"),
+    }
+}
+
+/// A repair droid driven by a single, persistent Intcode program.
+pub struct Droid {
+    program: Program,
+}
+
+impl Droid {
+    pub fn new(intcode: Vec<i64>) -> Self {
+        Self {
+            program: Program::new(intcode, vec![]).with_budget(DEFAULT_BUDGET),
+        }
+    }
+
+    pub fn from_file(path: &str) -> Self {
+        Self::new(read_intcode(path))
+    }
+
+    pub fn with_budget(mut self, steps: u64) -> Self {
+        self.program.set_budget(Some(steps));
+        self
+    }
+
+    pub fn send(&mut self, dir: u8) -> Tile {
+        self.program.get_pending_inputs().push(dir as i64);
+        self.program.run();
+        if self.program.status() == &Status::BudgetExhausted {
+            panic!("Droid did not finish within its step budget");
+        }
+        let status = self.program.read_all_outputs();
+        Tile::from_int(*status.last().expect("droid halted"))
+    }
+
+    pub fn explore(mut self) -> ShipMap {
+        explore(|dir| self.send(dir))
+    }
+}
+
+/// Maps every reachable tile with a depth-first walk, stepping back along the
+/// way it came once a tile has no unexplored neighbours left. `send` moves the
+/// droid one step and reports what it ran into.
+pub fn explore(mut send: impl FnMut(u8) -> Tile) -> ShipMap {
+    let mut tiles = HashMap::from([((0, 0), Tile::Open)]);
+    let mut stack: Vec<((i32, i32), usize, Option<u8>)> = vec![((0, 0), 0, None)];
+
+    while let Some((pos, next, back)) = stack.last_mut() {
+        let Some(&dir) = MOVES.get(*next) else {
+            if let Some(back) = *back {
+                send(back);
+            }
+            stack.pop();
+            continue;
+        };
+        *next += 1;
+        let target = step(*pos, dir);
+        if tiles.contains_key(&target) {
+            continue;
+        }
+        let tile = send(dir);
+        tiles.insert(target, tile);
+        if tile != Tile::Wall {
+            stack.push((target, 0, Some(opposite(dir))));
+        }
+    }
+    ShipMap::new(tiles)
+}
+
+pub struct ShipMap {
+    tiles: HashMap<(i32, i32), Tile>,
+    oxygen: Option<(i32, i32)>,
+}
+
+impl ShipMap {
+    pub fn new(tiles: HashMap<(i32, i32), Tile>) -> Self {
+        let oxygen = tiles
+            .iter()
+            .find(|(_, &t)| t == Tile::Oxygen)
+            .map(|(&pos, _)| pos);
+        Self { tiles, oxygen }
+    }
+
+    pub fn tiles(&self) -> &HashMap<(i32, i32), Tile> {
+        &self.tiles
+    }
+
+    pub fn oxygen(&self) -> Option<(i32, i32)> {
+        self.oxygen
+    }
+
+    pub fn distances_from(&self, start: (i32, i32)) -> HashMap<(i32, i32), u32> {
+        let mut dist = HashMap::from([(start, 0)]);
+        let mut q = VecDeque::from([start]);
+        while let Some(pos) = q.pop_front() {
+            let d = dist[&pos];
+            for dir in MOVES {
+                let next = step(pos, dir);
+                let open = matches!(self.tiles.get(&next), Some(Tile::Open | Tile::Oxygen));
+                if open && !dist.contains_key(&next) {
+                    dist.insert(next, d + 1);
+                    q.push_back(next);
+                }
+            }
+        }
+        dist
+    }
+
+    pub fn shortest_path(&self) -> Option<u32> {
+        self.distances_from((0, 0)).get(&self.oxygen?).copied()
+    }
+
+    pub fn fill_time(&self) -> Option<u32> {
+        self.distances_from(self.oxygen?).into_values().max()
+    }
+
+    pub fn render(&self) -> String {
+        let (min_x, max_x, min_y, max_y) = self.tiles.keys().fold(
+            (i32::MAX, i32::MIN, i32::MAX, i32::MIN),
+            |(lx, hx, ly, hy), &(x, y)| (lx.min(x), hx.max(x), ly.min(y), hy.max(y)),
+        );
+        (min_y..=max_y)
+            .map(|y| {
+                (min_x..=max_x)
+                    .map(|x| match self.tiles.get(&(x, y)) {
+                        _ if (x, y) == (0, 0) => 'D',
+                        Some(Tile::Wall) => '#',
+                        Some(Tile::Open) => '.',
+                        Some(Tile::Oxygen) => 'O',
+                        None => ' ',
+                    })
+                    .collect::<String>()
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+}
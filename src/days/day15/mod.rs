@@ -1,4 +1,5 @@
 #![allow(dead_code)]
+mod droid;
 mod part1;
 mod part2;
 
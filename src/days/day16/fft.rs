@@ -0,0 +1,128 @@
+/// Flawed Frequency Transmission over a (possibly repeated) input signal.
+pub struct Fft {
+    signal: Vec<u8>,
+    phases: usize,
+    repeat: usize,
+}
+
+impl Fft {
+    pub fn parse(input: &str) -> Self {
+        let signal = input
+            .trim()
+            .chars()
+            .map(|c| c.to_digit(10).expect("signal should be digits") as u8)
+            .collect::<Vec<_>>();
+        Self {
+            signal,
+            phases: 100,
+            repeat: 1,
+        }
+    }
+
+    pub fn from_file(path: &str) -> Self {
+        Self::parse(&std::fs::read_to_string(path).expect("File should be there"))
+    }
+
+    pub fn phases(mut self, phases: usize) -> Self {
+        self.phases = phases;
+        self
+    }
+
+    pub fn repeat(mut self, repeat: usize) -> Self {
+        self.repeat = repeat;
+        self
+    }
+
+    pub fn len(&self) -> usize {
+        self.signal.len() * self.repeat
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// The first seven digits of the raw signal, read as a number.
+    pub fn message_offset(&self) -> usize {
+        self.signal
+            .iter()
+            .take(7)
+            .fold(0, |acc, &d| acc * 10 + d as usize)
+    }
+
+    /// `len` digits of the final output starting at `offset`. Output digit `i`
+    /// only depends on input digits `i..`, so everything before `offset` is
+    /// dropped up front.
+    pub fn output_at(&self, offset: usize, len: usize) -> Vec<u8> {
+        let total = self.len();
+        assert!(
+            offset + len <= total,
+            "requested digits past the signal end"
+        );
+        let mut tail = (offset..total)
+            .map(|i| self.signal[i % self.signal.len()])
+            .collect::<Vec<_>>();
+
+        for _ in 0..self.phases {
+            tail = if offset * 2 >= total {
+                suffix_phase(&tail)
+            } else {
+                prefix_phase(&tail, offset)
+            };
+        }
+        tail.truncate(len);
+        tail
+    }
+
+    pub fn output(&self) -> Vec<u8> {
+        self.output_at(0, self.len())
+    }
+
+    pub fn message(&self) -> Vec<u8> {
+        self.output_at(self.message_offset(), 8)
+    }
+}
+
+pub fn to_number(digits: &[u8]) -> u32 {
+    digits.iter().fold(0, |acc, &d| acc * 10 + d as u32)
+}
+
+/// In the second half of the signal every pattern is zeros followed by ones,
+/// so each digit is just the sum of everything after it.
+fn suffix_phase(tail: &[u8]) -> Vec<u8> {
+    let mut out = vec![0; tail.len()];
+    let mut sum = 0;
+    for i in (0..tail.len()).rev() {
+        sum = (sum + tail[i]) % 10;
+        out[i] = sum;
+    }
+    out
+}
+
+/// Sums whole `+1`/`-1` blocks of the `0,1,0,-1` pattern from prefix sums.
+/// `tail[0]` sits at position `offset` of the full signal.
+fn prefix_phase(tail: &[u8], offset: usize) -> Vec<u8> {
+    let n = tail.len();
+    let mut prefix = vec![0i64; n + 1];
+    for (i, &d) in tail.iter().enumerate() {
+        prefix[i + 1] = prefix[i] + d as i64;
+    }
+    let block_sum = |start: usize, width: usize| {
+        let lo = start.min(n);
+        let hi = (start + width).min(n);
+        prefix[hi] - prefix[lo]
+    };
+
+    (0..n)
+        .map(|local| {
+            let width = offset + local + 1;
+            let mut total = 0;
+            let mut start = local;
+            while start < n {
+                total += block_sum(start, width);
+                total -= block_sum(start + 2 * width, width);
+                start += 4 * width;
+            }
+            (total.abs() % 10) as u8
+        })
+        .collect()
+}
@@ -0,0 +1,87 @@
+use super::scaffold::Move;
+
+pub const MAX_ROUTINE_LEN: usize = 20;
+const NAMES: [char; 3] = ['A', 'B', 'C'];
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Routines {
+    pub main: String,
+    pub functions: [String; 3],
+}
+
+impl Routines {
+    /// Lines to feed the robot, in prompt order, without the video answer.
+    pub fn lines(&self) -> Vec<&str> {
+        let mut lines = vec![self.main.as_str()];
+        lines.extend(self.functions.iter().map(|f| f.as_str()));
+        lines
+    }
+}
+
+/// Splits `path` into a main routine calling at most three movement functions
+/// so that no routine is longer than `max_len` characters.
+pub fn compress(path: &[Move], max_len: usize) -> Option<Routines> {
+    let mut functions: Vec<&[Move]> = vec![];
+    let mut calls: Vec<usize> = vec![];
+    if !search(path, max_len, &mut functions, &mut calls) {
+        return None;
+    }
+
+    let main = calls
+        .iter()
+        .map(|&f| NAMES[f].to_string())
+        .collect::<Vec<_>>()
+        .join(",");
+    let mut bodies = functions.iter().map(|f| encode(f));
+    let functions = std::array::from_fn(|_| bodies.next().unwrap_or_default());
+    Some(Routines { main, functions })
+}
+
+fn search<'a>(
+    rest: &'a [Move],
+    max_len: usize,
+    functions: &mut Vec<&'a [Move]>,
+    calls: &mut Vec<usize>,
+) -> bool {
+    if rest.is_empty() {
+        return true;
+    }
+    if calls.len() * 2 + 1 > max_len {
+        return false;
+    }
+
+    for f in 0..functions.len() {
+        if rest.starts_with(functions[f]) {
+            calls.push(f);
+            if search(&rest[functions[f].len()..], max_len, functions, calls) {
+                return true;
+            }
+            calls.pop();
+        }
+    }
+
+    if functions.len() < NAMES.len() {
+        for len in 1..=rest.len() {
+            let body = &rest[..len];
+            if encode(body).len() > max_len {
+                break;
+            }
+            functions.push(body);
+            calls.push(functions.len() - 1);
+            if search(&rest[len..], max_len, functions, calls) {
+                return true;
+            }
+            calls.pop();
+            functions.pop();
+        }
+    }
+    false
+}
+
+fn encode(moves: &[Move]) -> String {
+    moves
+        .iter()
+        .map(|m| m.to_string())
+        .collect::<Vec<_>>()
+        .join(",")
+}
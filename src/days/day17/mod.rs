@@ -1,6 +1,9 @@
 #![allow(dead_code)]
+mod compress;
 mod part1;
 mod part2;
+mod robot;
+mod scaffold;
 
 pub fn run() {
     println!("====================Day 17====================");
@@ -8,3 +11,8 @@ pub fn run() {
     println!("Part 2: {}", part2::part2("inputs/day17"));
     println!("==============================================");
 }
+
+/// Walks the robot with its video feed on and shows what the camera sees.
+pub fn watch() {
+    println!("Dust: {}", part2::watch("inputs/day17"));
+}
@@ -0,0 +1,61 @@
+use super::compress::Routines;
+use super::scaffold::Scaffold;
+use crate::intcode::ascii::AsciiProgram;
+use crate::intcode::patch::Patch;
+use crate::intcode::{read_intcode, Program};
+
+pub struct RobotReport {
+    pub dust: i64,
+    pub frames: Vec<Scaffold>,
+}
+
+pub struct VacuumRobot {
+    intcode: Vec<i64>,
+}
+
+impl VacuumRobot {
+    pub fn new(intcode: Vec<i64>) -> Self {
+        Self { intcode }
+    }
+
+    pub fn from_file(path: &str) -> Self {
+        Self::new(read_intcode(path))
+    }
+
+    pub fn camera(&self) -> Scaffold {
+        let view = AsciiProgram::new(Program::new(self.intcode.clone(), vec![]))
+            .read_text()
+            .unwrap_or_else(|e| panic!("Camera failed: {}", e));
+        Scaffold::from_ascii(&view)
+    }
+
+    /// Wakes the robot up and walks it through `routines`. With the video
+    /// feed on, every camera frame it prints along the way is kept.
+    pub fn run(&self, routines: &Routines, video_feed: bool) -> RobotReport {
+        let program =
+            Program::new(self.intcode.clone(), vec![]).with_patch(&Patch::set("wake up", 0, 2));
+        let feed = if video_feed { "y" } else { "n" };
+        let mut robot = AsciiProgram::new(program);
+        for line in routines.lines().into_iter().chain([feed]) {
+            robot
+                .send_line(line)
+                .unwrap_or_else(|e| panic!("Invalid routine: {}", e));
+        }
+        let dust = robot
+            .take_nonascii_result()
+            .unwrap_or_else(|e| panic!("Robot did not report dust: {}", e));
+        let text = robot
+            .read_text()
+            .unwrap_or_else(|e| panic!("Robot failed: {}", e));
+
+        let frames = if video_feed {
+            text.split("\n\n")
+                .filter(|chunk| Scaffold::is_frame(chunk))
+                .map(Scaffold::from_ascii)
+                .collect()
+        } else {
+            vec![]
+        };
+        RobotReport { dust, frames }
+    }
+}
@@ -0,0 +1,121 @@
+use std::fmt;
+
+const DIRS: [(i32, i32); 4] = [(-1, 0), (0, 1), (1, 0), (0, -1)];
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Move {
+    pub turn: char,
+    pub steps: u32,
+}
+
+impl fmt::Display for Move {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{},{}", self.turn, self.steps)
+    }
+}
+
+/// One camera frame: the scaffold layout plus the robot, if it is visible.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Scaffold {
+    grid: Vec<Vec<char>>,
+    robot: Option<((usize, usize), char)>,
+}
+
+impl Scaffold {
+    pub fn from_ascii(view: &str) -> Self {
+        let grid = view
+            .lines()
+            .filter(|l| !l.is_empty())
+            .map(|l| l.chars().collect::<Vec<_>>())
+            .collect::<Vec<_>>();
+        let robot = grid.iter().enumerate().find_map(|(r, row)| {
+            row.iter()
+                .position(|c| "^>v<X".contains(*c))
+                .map(|c| ((r, c), row[c]))
+        });
+        Self { grid, robot }
+    }
+
+    /// Whether every line of `text` is made of camera characters only.
+    pub fn is_frame(text: &str) -> bool {
+        text.lines().any(|l| !l.is_empty())
+            && text
+                .lines()
+                .all(|l| l.chars().all(|c| "#.^>v<X".contains(c)))
+    }
+
+    fn is_scaffold(&self, r: i32, c: i32) -> bool {
+        r >= 0
+            && c >= 0
+            && self
+                .grid
+                .get(r as usize)
+                .and_then(|row| row.get(c as usize))
+                .is_some_and(|&ch| ch != '.')
+    }
+
+    pub fn intersections(&self) -> Vec<(usize, usize)> {
+        let mut points = vec![];
+        for (r, row) in self.grid.iter().enumerate() {
+            for c in 0..row.len() {
+                let (ri, ci) = (r as i32, c as i32);
+                if self.is_scaffold(ri, ci)
+                    && DIRS
+                        .iter()
+                        .all(|(dr, dc)| self.is_scaffold(ri + dr, ci + dc))
+                {
+                    points.push((r, c));
+                }
+            }
+        }
+        points
+    }
+
+    pub fn alignment_sum(&self) -> usize {
+        self.intersections().iter().map(|(r, c)| r * c).sum()
+    }
+
+    /// Walks straight ahead as far as possible and only turns at corners,
+    /// which covers the whole scaffold for puzzle inputs. Stops after as many
+    /// moves as there are scaffold tiles so a closed loop cannot spin forever.
+    pub fn path(&self) -> Vec<Move> {
+        let Some(((r, c), facing)) = self.robot else {
+            return vec![];
+        };
+        let mut dir = match facing {
+            '^' => 0,
+            '>' => 1,
+            'v' => 2,
+            '<' => 3,
+            _ => return vec![],
+        };
+        let (mut r, mut c) = (r as i32, c as i32);
+        let limit = self.grid.iter().flatten().filter(|&&ch| ch != '.').count();
+        let mut path = vec![];
+        while path.len() < limit {
+            let Some((turn, new_dir)) = [('L', (dir + 3) % 4), ('R', (dir + 1) % 4)]
+                .into_iter()
+                .find(|&(_, d)| self.is_scaffold(r + DIRS[d].0, c + DIRS[d].1))
+            else {
+                break;
+            };
+            dir = new_dir;
+            let mut steps = 0;
+            while self.is_scaffold(r + DIRS[dir].0, c + DIRS[dir].1) {
+                r += DIRS[dir].0;
+                c += DIRS[dir].1;
+                steps += 1;
+            }
+            path.push(Move { turn, steps });
+        }
+        path
+    }
+
+    pub fn render(&self) -> String {
+        self.grid
+            .iter()
+            .map(|row| row.iter().collect::<String>())
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+}
@@ -1,6 +1,7 @@
 #![allow(dead_code)]
 mod part1;
 mod part2;
+mod vault;
 
 pub fn run() {
     println!("====================Day 18====================");
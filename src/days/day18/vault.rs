@@ -0,0 +1,222 @@
+use std::collections::{BinaryHeap, HashMap, HashSet, VecDeque};
+
+const DIRS: [(i8, i8); 4] = [(-1, 0), (0, 1), (1, 0), (0, -1)];
+const A_UPPER: u32 = 'A' as u32;
+const A_LOWER: u32 = 'a' as u32;
+
+#[derive(Clone, Copy, Debug)]
+pub struct Edge {
+    pub to: usize,
+    pub steps: u32,
+    pub doors: u32,
+}
+
+#[derive(Debug, PartialEq)]
+pub struct Solution {
+    pub steps: u32,
+    pub order: Vec<char>,
+}
+
+#[derive(Clone, Eq, Hash, PartialEq)]
+struct Robots {
+    nodes: Vec<usize>,
+    keys: u32,
+}
+
+#[derive(Eq, PartialEq)]
+struct State {
+    robots: Robots,
+    steps: u32,
+}
+
+impl Ord for State {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        other.steps.cmp(&self.steps)
+    }
+}
+
+impl PartialOrd for State {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+/// A vault with any number of `@` entrances. Graph nodes are the entrances
+/// first, followed by the keys in the order they appear in the map.
+pub struct Vault {
+    grid: Vec<Vec<char>>,
+    starts: Vec<(usize, usize)>,
+    keys: Vec<(char, (usize, usize))>,
+}
+
+impl Vault {
+    pub fn parse(input: &str) -> Self {
+        let grid = input
+            .trim()
+            .lines()
+            .map(|l| l.chars().collect::<Vec<_>>())
+            .collect::<Vec<_>>();
+        Self::from_grid(grid)
+    }
+
+    pub fn from_file(path: &str) -> Self {
+        Self::parse(&std::fs::read_to_string(path).expect("File should be there"))
+    }
+
+    fn from_grid(grid: Vec<Vec<char>>) -> Self {
+        let mut starts = vec![];
+        let mut keys = vec![];
+        for (i, row) in grid.iter().enumerate() {
+            for (j, &c) in row.iter().enumerate() {
+                match c {
+                    '@' => starts.push((i, j)),
+                    'a'..='z' => keys.push((c, (i, j))),
+                    _ => {}
+                }
+            }
+        }
+        Self { grid, starts, keys }
+    }
+
+    /// Replaces a lone entrance with four robots walled off from each other.
+    /// Vaults that already have several entrances, or whose entrance is too
+    /// close to the edge to fit the walls around it, are returned unchanged.
+    pub fn quartered(&self) -> Self {
+        let mut grid = self.grid.clone();
+        if let [(r, c)] = self.starts[..] {
+            let fits = r >= 1
+                && c >= 1
+                && (r - 1..=r + 1).all(|i| grid.get(i).is_some_and(|row| row.len() > c + 1));
+            if !fits {
+                return Self::from_grid(grid);
+            }
+            let patch = ["@#@", "###", "@#@"];
+            for (dr, row) in patch.iter().enumerate() {
+                for (dc, ch) in row.chars().enumerate() {
+                    grid[r + dr - 1][c + dc - 1] = ch;
+                }
+            }
+        }
+        Self::from_grid(grid)
+    }
+
+    pub fn starts(&self) -> &[(usize, usize)] {
+        &self.starts
+    }
+
+    fn node_pos(&self, node: usize) -> (usize, usize) {
+        if node < self.starts.len() {
+            self.starts[node]
+        } else {
+            self.keys[node - self.starts.len()].1
+        }
+    }
+
+    fn key_bit(&self, node: usize) -> u32 {
+        1 << (self.keys[node - self.starts.len()].0 as u32 - A_LOWER)
+    }
+
+    /// Shortest distance from every entrance and key to every key it can
+    /// reach, along with the doors that stand in the way.
+    pub fn key_graph(&self) -> Vec<Vec<Edge>> {
+        let key_nodes = self
+            .keys
+            .iter()
+            .enumerate()
+            .map(|(i, &(_, pos))| (pos, self.starts.len() + i))
+            .collect::<HashMap<_, _>>();
+
+        (0..self.starts.len() + self.keys.len())
+            .map(|from| {
+                let start = self.node_pos(from);
+                let mut edges = vec![];
+                let mut seen = HashSet::from([start]);
+                let mut q = VecDeque::from([(start, 0, 0u32)]);
+                while let Some((pos, steps, doors)) = q.pop_front() {
+                    if let Some(&to) = key_nodes.get(&pos) {
+                        if to != from {
+                            edges.push(Edge { to, steps, doors });
+                        }
+                    }
+                    for dir in DIRS {
+                        let (Some(nr), Some(nc)) = (
+                            pos.0.checked_add_signed(dir.0 as isize),
+                            pos.1.checked_add_signed(dir.1 as isize),
+                        ) else {
+                            continue;
+                        };
+                        let next = (nr, nc);
+                        let mut doors = doors;
+                        // Anything off the map, including past the end of a
+                        // short row, is wall.
+                        match self.grid.get(nr).and_then(|row| row.get(nc)) {
+                            None | Some('#') => continue,
+                            Some(&c @ 'A'..='Z') => doors |= 1 << (c as u32 - A_UPPER),
+                            _ => {}
+                        }
+                        if seen.insert(next) {
+                            q.push_back((next, steps + 1, doors));
+                        }
+                    }
+                }
+                edges
+            })
+            .collect()
+    }
+
+    /// Fewest total steps for the robots to collect every key, and the order
+    /// the keys are picked up in.
+    pub fn solve(&self) -> Option<Solution> {
+        let graph = self.key_graph();
+        let all_keys = (self.starts.len()..graph.len()).fold(0, |m, n| m | self.key_bit(n));
+        let start = Robots {
+            nodes: (0..self.starts.len()).collect(),
+            keys: 0,
+        };
+
+        let mut dist: HashMap<Robots, u32> = HashMap::from([(start.clone(), 0)]);
+        let mut prev: HashMap<Robots, (Robots, usize)> = HashMap::new();
+        let mut q = BinaryHeap::from([State {
+            robots: start,
+            steps: 0,
+        }]);
+
+        while let Some(State { robots, steps }) = q.pop() {
+            if robots.keys == all_keys {
+                let mut order = vec![];
+                let mut current = &robots;
+                while let Some((before, node)) = prev.get(current) {
+                    order.push(self.keys[node - self.starts.len()].0);
+                    current = before;
+                }
+                order.reverse();
+                return Some(Solution { steps, order });
+            }
+            if dist.get(&robots).is_some_and(|&d| d < steps) {
+                continue;
+            }
+
+            for (i, &node) in robots.nodes.iter().enumerate() {
+                for edge in &graph[node] {
+                    let bit = self.key_bit(edge.to);
+                    if robots.keys & bit != 0 || edge.doors & !robots.keys != 0 {
+                        continue;
+                    }
+                    let mut next = robots.clone();
+                    next.nodes[i] = edge.to;
+                    next.keys |= bit;
+                    let next_steps = steps + edge.steps;
+                    if dist.get(&next).is_none_or(|&d| next_steps < d) {
+                        dist.insert(next.clone(), next_steps);
+                        prev.insert(next.clone(), (robots.clone(), edge.to));
+                        q.push(State {
+                            robots: next,
+                            steps: next_steps,
+                        });
+                    }
+                }
+            }
+        }
+        None
+    }
+}
@@ -0,0 +1,117 @@
+use std::collections::HashMap;
+
+use crate::intcode::{Program, Status};
+
+const MIN_SCAN: u64 = 10;
+
+/// Steps a single drone deployment may take.
+pub const PROBE_BUDGET: u64 = 1_000_000;
+
+/// Tracks the left and right edge of the tractor beam row by row. Each row's
+/// search starts from the previous row's edges, so only a handful of cells
+/// per row ever get probed.
+pub struct TractorBeam<P: FnMut(u64, u64) -> bool> {
+    probe: P,
+    cache: HashMap<(u64, u64), bool>,
+    rows: Vec<Option<(u64, u64)>>,
+    probes: usize,
+}
+
+pub fn intcode_probe(intcode: Vec<i64>) -> impl FnMut(u64, u64) -> bool {
+    move |x, y| {
+        let mut program =
+            Program::new(intcode.clone(), vec![x as i64, y as i64]).with_budget(PROBE_BUDGET);
+        program.run();
+        if program.status() == &Status::BudgetExhausted {
+            panic!("Drone did not report within its step budget");
+        }
+        *program
+            .read_all_outputs()
+            .first()
+            .expect("drone should report")
+            == 1
+    }
+}
+
+impl<P: FnMut(u64, u64) -> bool> TractorBeam<P> {
+    pub fn new(probe: P) -> Self {
+        Self {
+            probe,
+            cache: HashMap::new(),
+            rows: vec![],
+            probes: 0,
+        }
+    }
+
+    pub fn is_pulled(&mut self, x: u64, y: u64) -> bool {
+        if let Some(&pulled) = self.cache.get(&(x, y)) {
+            return pulled;
+        }
+        self.probes += 1;
+        let pulled = (self.probe)(x, y);
+        self.cache.insert((x, y), pulled);
+        pulled
+    }
+
+    /// Number of times the underlying probe has actually been run.
+    pub fn probes(&self) -> usize {
+        self.probes
+    }
+
+    /// Inclusive `(left, right)` beam edges on row `y`, or `None` if the beam
+    /// is too thin to hit any cell there. A row counts as empty when nothing
+    /// turns up within `max(y, 10)` cells of the last known left edge.
+    pub fn row(&mut self, y: u64) -> Option<(u64, u64)> {
+        while self.rows.len() as u64 <= y {
+            let row = self.rows.len() as u64;
+            let edges = self.find_row(row);
+            self.rows.push(edges);
+        }
+        self.rows[y as usize]
+    }
+
+    fn find_row(&mut self, y: u64) -> Option<(u64, u64)> {
+        let (left_hint, right_hint) = self.rows.iter().rev().find_map(|r| *r).unwrap_or((0, 0));
+        let left = (left_hint..=left_hint + y.max(MIN_SCAN)).find(|&x| self.is_pulled(x, y))?;
+        let mut right = right_hint.max(left);
+        if !self.is_pulled(right, y) {
+            right = left;
+        }
+        while self.is_pulled(right + 1, y) {
+            right += 1;
+        }
+        Some((left, right))
+    }
+
+    /// Top-left corner of the square of side `size` closest to the emitter
+    /// that fits entirely inside the beam, searching down to row `max_y`.
+    pub fn closest_square(&mut self, size: u64, max_y: u64) -> Option<(u64, u64)> {
+        if size == 0 {
+            return Some((0, 0));
+        }
+        for bottom in size - 1..=max_y {
+            let Some((left, _)) = self.row(bottom) else {
+                continue;
+            };
+            let top = bottom + 1 - size;
+            if let Some((top_left, top_right)) = self.row(top) {
+                if top_left <= left && top_right >= left + size - 1 {
+                    return Some((left, top));
+                }
+            }
+        }
+        None
+    }
+
+    /// Number of pulled points with `x0 <= x < x1` and `y0 <= y < y1`.
+    pub fn affected_in(&mut self, x0: u64, y0: u64, x1: u64, y1: u64) -> u64 {
+        (y0..y1)
+            .filter_map(|y| self.row(y))
+            .map(|(left, right)| {
+                let lo = left.max(x0);
+                let hi = (right + 1).min(x1);
+                hi.saturating_sub(lo)
+            })
+            .sum()
+    }
+}
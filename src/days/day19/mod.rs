@@ -1,4 +1,5 @@
 #![allow(dead_code)]
+mod beam;
 mod part1;
 mod part2;
 
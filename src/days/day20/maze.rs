@@ -0,0 +1,255 @@
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap, HashSet, VecDeque};
+use std::fmt;
+
+const DIRS: [(i8, i8); 4] = [(-1, 0), (0, 1), (1, 0), (0, -1)];
+
+/// Maps each `(portal, depth)` state to the state it was reached from and
+/// whether that move went through a portal.
+type Trail = HashMap<(usize, usize), ((usize, usize), bool)>;
+
+#[derive(Debug, PartialEq)]
+pub enum MazeError {
+    MissingPortal(&'static str),
+    UnpairedPortal(String),
+}
+
+impl fmt::Display for MazeError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            MazeError::MissingPortal(label) => write!(f, "maze has no {} tile", label),
+            MazeError::UnpairedPortal(label) => {
+                write!(f, "portal {} does not have exactly two ends", label)
+            }
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Mode {
+    Flat,
+    Recursive { max_depth: usize },
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Portal {
+    pub label: String,
+    pub pos: (usize, usize),
+    pub outer: bool,
+}
+
+/// A portal stepped through and the depth it led to.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Hop {
+    pub label: String,
+    pub depth: usize,
+}
+
+#[derive(Debug, PartialEq)]
+pub struct Route {
+    pub steps: u32,
+    pub hops: Vec<Hop>,
+}
+
+pub struct DonutMaze {
+    portals: Vec<Portal>,
+    partner: Vec<Option<usize>>,
+    edges: Vec<Vec<(usize, u32)>>,
+    start: usize,
+    end: usize,
+}
+
+impl DonutMaze {
+    pub fn parse(input: &str) -> Result<Self, MazeError> {
+        let mut grid = input
+            .lines()
+            .map(|l| l.chars().collect::<Vec<_>>())
+            .collect::<Vec<_>>();
+        let width = grid.iter().map(|r| r.len()).max().unwrap_or(0);
+        for row in grid.iter_mut() {
+            row.resize(width, ' ');
+        }
+
+        let portals = find_portals(&grid);
+        let find = |label: &'static str| {
+            portals
+                .iter()
+                .position(|p| p.label == label)
+                .ok_or(MazeError::MissingPortal(label))
+        };
+        let start = find("AA")?;
+        let end = find("ZZ")?;
+
+        let mut partner = vec![None; portals.len()];
+        for (i, portal) in portals.iter().enumerate() {
+            if i == start || i == end {
+                continue;
+            }
+            let others = portals
+                .iter()
+                .enumerate()
+                .filter(|&(j, p)| j != i && p.label == portal.label)
+                .map(|(j, _)| j)
+                .collect::<Vec<_>>();
+            match others[..] {
+                [j] => partner[i] = Some(j),
+                _ => return Err(MazeError::UnpairedPortal(portal.label.clone())),
+            }
+        }
+
+        let edges = portals
+            .iter()
+            .map(|p| walk_from(&grid, p.pos, &portals))
+            .collect();
+        Ok(Self {
+            portals,
+            partner,
+            edges,
+            start,
+            end,
+        })
+    }
+
+    pub fn from_file(path: &str) -> Result<Self, MazeError> {
+        Self::parse(&std::fs::read_to_string(path).expect("File should be there"))
+    }
+
+    pub fn portals(&self) -> &[Portal] {
+        &self.portals
+    }
+
+    /// Walking distances between portal tiles, indexed like `portals`.
+    pub fn edges(&self) -> &[Vec<(usize, u32)>] {
+        &self.edges
+    }
+
+    /// Shortest route from AA to ZZ. In recursive mode inner portals lead one
+    /// level down, outer portals one level up, and only level 0 has an exit.
+    pub fn solve(&self, mode: Mode) -> Option<Route> {
+        let mut dist: HashMap<(usize, usize), u32> = HashMap::from([((self.start, 0), 0)]);
+        let mut prev: Trail = HashMap::new();
+        let mut q = BinaryHeap::from([Reverse((0, self.start, 0))]);
+
+        while let Some(Reverse((steps, node, depth))) = q.pop() {
+            if node == self.end && depth == 0 {
+                return Some(Route {
+                    steps,
+                    hops: self.hops(&prev, (node, depth)),
+                });
+            }
+            if dist.get(&(node, depth)).is_some_and(|&d| d < steps) {
+                continue;
+            }
+
+            let mut moves = self.edges[node]
+                .iter()
+                .map(|&(to, d)| (to, depth, steps + d, false))
+                .collect::<Vec<_>>();
+            if let Some(to) = self.partner[node] {
+                let next_depth = match mode {
+                    Mode::Flat => Some(0),
+                    Mode::Recursive { .. } if self.portals[node].outer => depth.checked_sub(1),
+                    Mode::Recursive { max_depth } => Some(depth + 1).filter(|&d| d <= max_depth),
+                };
+                if let Some(next_depth) = next_depth {
+                    moves.push((to, next_depth, steps + 1, true));
+                }
+            }
+
+            for (to, to_depth, to_steps, warp) in moves {
+                if dist.get(&(to, to_depth)).is_none_or(|&d| to_steps < d) {
+                    dist.insert((to, to_depth), to_steps);
+                    prev.insert((to, to_depth), ((node, depth), warp));
+                    q.push(Reverse((to_steps, to, to_depth)));
+                }
+            }
+        }
+        None
+    }
+
+    fn hops(&self, prev: &Trail, mut state: (usize, usize)) -> Vec<Hop> {
+        let mut hops = vec![];
+        while let Some(&(before, warp)) = prev.get(&state) {
+            if warp {
+                hops.push(Hop {
+                    label: self.portals[state.0].label.clone(),
+                    depth: state.1,
+                });
+            }
+            state = before;
+        }
+        hops.reverse();
+        hops
+    }
+}
+
+fn find_portals(grid: &[Vec<char>]) -> Vec<Portal> {
+    let open = |r: usize, c: usize| grid.get(r).and_then(|row| row.get(c)) == Some(&'.');
+    let mut body = (usize::MAX, 0, usize::MAX, 0);
+    for (r, row) in grid.iter().enumerate() {
+        for (c, &ch) in row.iter().enumerate() {
+            if ch == '#' || ch == '.' {
+                body = (body.0.min(r), body.1.max(r), body.2.min(c), body.3.max(c));
+            }
+        }
+    }
+
+    let mut portals = vec![];
+    for (r, row) in grid.iter().enumerate() {
+        for (c, &first) in row.iter().enumerate() {
+            if !first.is_ascii_uppercase() {
+                continue;
+            }
+            let mut candidates = vec![];
+            if grid.get(r + 1).is_some_and(|n| n[c].is_ascii_uppercase()) {
+                let label = format!("{}{}", first, grid[r + 1][c]);
+                if r > 0 && open(r - 1, c) {
+                    candidates.push((label.clone(), (r - 1, c)));
+                }
+                if open(r + 2, c) {
+                    candidates.push((label, (r + 2, c)));
+                }
+            }
+            if row.get(c + 1).is_some_and(|n| n.is_ascii_uppercase()) {
+                let label = format!("{}{}", first, row[c + 1]);
+                if c > 0 && open(r, c - 1) {
+                    candidates.push((label.clone(), (r, c - 1)));
+                }
+                if open(r, c + 2) {
+                    candidates.push((label, (r, c + 2)));
+                }
+            }
+            for (label, pos) in candidates {
+                let outer =
+                    pos.0 == body.0 || pos.0 == body.1 || pos.1 == body.2 || pos.1 == body.3;
+                portals.push(Portal { label, pos, outer });
+            }
+        }
+    }
+    portals
+}
+
+fn walk_from(grid: &[Vec<char>], start: (usize, usize), portals: &[Portal]) -> Vec<(usize, u32)> {
+    let mut edges = vec![];
+    let mut seen = HashSet::from([start]);
+    let mut q = VecDeque::from([(start, 0)]);
+    while let Some((pos, steps)) = q.pop_front() {
+        if let Some(i) = portals.iter().position(|p| p.pos == pos) {
+            if pos != start {
+                edges.push((i, steps));
+            }
+        }
+        for dir in DIRS {
+            let (nr, nc) = (pos.0 as i32 + dir.0 as i32, pos.1 as i32 + dir.1 as i32);
+            if nr < 0 || nc < 0 {
+                continue;
+            }
+            let next = (nr as usize, nc as usize);
+            let open = grid.get(next.0).and_then(|row| row.get(next.1)) == Some(&'.');
+            if open && seen.insert(next) {
+                q.push_back((next, steps + 1));
+            }
+        }
+    }
+    edges
+}
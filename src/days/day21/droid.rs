@@ -0,0 +1,82 @@
+use super::springscript::{Hull, Mode, Script};
+use super::synthesis::synthesize;
+use crate::intcode::ascii::{AsciiError, AsciiProgram};
+use crate::intcode::{read_intcode, Program};
+
+/// Steps a single run may take before the droid is given up on.
+pub const DEFAULT_BUDGET: u64 = 100_000_000;
+
+#[derive(Debug, PartialEq)]
+pub enum Outcome {
+    /// The droid made it across and reported the hull damage.
+    Damage(i64),
+    /// The droid fell into space while crossing this stretch of hull.
+    Fell(Hull),
+    /// The run used up its step budget without finishing.
+    TimedOut,
+}
+
+pub struct Springdroid {
+    intcode: Vec<i64>,
+    budget: u64,
+}
+
+impl Springdroid {
+    pub fn new(intcode: Vec<i64>) -> Self {
+        Self {
+            intcode,
+            budget: DEFAULT_BUDGET,
+        }
+    }
+
+    pub fn from_file(path: &str) -> Self {
+        Self::new(read_intcode(path))
+    }
+
+    pub fn with_budget(mut self, steps: u64) -> Self {
+        self.budget = steps;
+        self
+    }
+
+    pub fn run(&self, script: &Script) -> Outcome {
+        let program = Program::new(self.intcode.clone(), vec![]).with_budget(self.budget);
+        let mut droid = AsciiProgram::new(program);
+        let result = droid
+            .read_until_prompt("Input instructions:\n")
+            .and_then(|_| {
+                for line in script.to_springcode().lines() {
+                    droid.send_line(line)?;
+                }
+                droid.take_nonascii_result()
+            });
+        let text = match result {
+            Ok(damage) => return Outcome::Damage(damage),
+            Err(AsciiError::BudgetExhausted(_)) => return Outcome::TimedOut,
+            Err(AsciiError::NoResult(text)) => text,
+            Err(e) => panic!("Droid failed: {}", e),
+        };
+        let row = text
+            .lines()
+            .rfind(|l| l.contains('#') && l.chars().all(|c| "#.@".contains(c)))
+            .unwrap_or_else(|| panic!("Droid output has no hull:\n{}", text));
+        Outcome::Fell(Hull::parse(row))
+    }
+
+    /// Alternates between synthesizing a script for every hull seen so far
+    /// and sending the droid out with it, until it survives. Gives up when
+    /// no script fits, the droid falls on a hull it already passes or runs
+    /// out of steps, or after `max_rounds` attempts.
+    pub fn learn(&self, mode: Mode, max_rounds: usize) -> Option<(Script, i64)> {
+        let mut hulls: Vec<Hull> = vec![];
+        for _ in 0..max_rounds {
+            let script = synthesize(&hulls, mode)?;
+            match self.run(&script) {
+                Outcome::Damage(damage) => return Some((script, damage)),
+                Outcome::Fell(hull) if hulls.contains(&hull) => return None,
+                Outcome::Fell(hull) => hulls.push(hull),
+                Outcome::TimedOut => return None,
+            }
+        }
+        None
+    }
+}
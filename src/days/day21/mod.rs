@@ -1,6 +1,9 @@
 #![allow(dead_code)]
+mod droid;
 mod part1;
 mod part2;
+mod springscript;
+mod synthesis;
 
 pub fn run() {
     println!("====================Day 21====================");
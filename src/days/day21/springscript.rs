@@ -0,0 +1,425 @@
+use std::fmt;
+
+pub const MAX_INSTRUCTIONS: usize = 15;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Mode {
+    Walk,
+    Run,
+}
+
+impl Mode {
+    /// How many hull tiles ahead the droid can sense.
+    pub fn range(self) -> usize {
+        match self {
+            Mode::Walk => 4,
+            Mode::Run => 9,
+        }
+    }
+
+    /// How far a jump carries the droid.
+    pub fn jump(self) -> usize {
+        4
+    }
+}
+
+impl fmt::Display for Mode {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Mode::Walk => write!(f, "WALK"),
+            Mode::Run => write!(f, "RUN"),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Register {
+    Sensor(u8),
+    Temp,
+    Jump,
+}
+
+impl Register {
+    pub fn from_char(c: char) -> Option<Self> {
+        match c {
+            'A'..='I' => Some(Register::Sensor(c as u8 - b'A')),
+            'T' => Some(Register::Temp),
+            'J' => Some(Register::Jump),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for Register {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Register::Sensor(i) => write!(f, "{}", (b'A' + i) as char),
+            Register::Temp => write!(f, "T"),
+            Register::Jump => write!(f, "J"),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Op {
+    And,
+    Or,
+    Not,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Instruction {
+    pub op: Op,
+    pub x: Register,
+    pub y: Register,
+}
+
+impl fmt::Display for Instruction {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let op = match self.op {
+            Op::And => "AND",
+            Op::Or => "OR",
+            Op::Not => "NOT",
+        };
+        write!(f, "{} {} {}", op, self.x, self.y)
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub enum ScriptError {
+    Parse { line: usize, text: String },
+    MissingMode,
+    TooLong(usize),
+    ReadOnly { line: usize, register: Register },
+    OutOfRange { line: usize, register: Register },
+}
+
+impl fmt::Display for ScriptError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ScriptError::Parse { line, text } => {
+                write!(f, "line {}: cannot parse {:?}", line, text)
+            }
+            ScriptError::MissingMode => write!(f, "script must end with WALK or RUN"),
+            ScriptError::TooLong(n) => write!(
+                f,
+                "{} instructions exceed the limit of {}",
+                n, MAX_INSTRUCTIONS
+            ),
+            ScriptError::ReadOnly { line, register } => {
+                write!(f, "line {}: cannot write to {}", line, register)
+            }
+            ScriptError::OutOfRange { line, register } => {
+                write!(f, "line {}: sensor {} is out of range", line, register)
+            }
+        }
+    }
+}
+
+/// A springscript program. Sensor readings are passed around as bitmasks
+/// with bit 0 for `A`, bit 1 for `B` and so on; a set bit means ground.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Script {
+    pub instructions: Vec<Instruction>,
+    pub mode: Mode,
+}
+
+impl Script {
+    pub fn parse(text: &str) -> Result<Self, ScriptError> {
+        let mut instructions = vec![];
+        for (i, line) in text.lines().enumerate() {
+            let line = line.trim();
+            let err = || ScriptError::Parse {
+                line: i + 1,
+                text: line.to_string(),
+            };
+            let words = line.split_whitespace().collect::<Vec<_>>();
+            let mode = match words[..] {
+                [] => continue,
+                ["WALK"] => Mode::Walk,
+                ["RUN"] => Mode::Run,
+                [op, x, y] => {
+                    let op = match op {
+                        "AND" => Op::And,
+                        "OR" => Op::Or,
+                        "NOT" => Op::Not,
+                        _ => return Err(err()),
+                    };
+                    let register = |s: &str| {
+                        let mut chars = s.chars();
+                        match (chars.next(), chars.next()) {
+                            (Some(c), None) => Register::from_char(c).ok_or_else(err),
+                            _ => Err(err()),
+                        }
+                    };
+                    instructions.push(Instruction {
+                        op,
+                        x: register(x)?,
+                        y: register(y)?,
+                    });
+                    continue;
+                }
+                _ => return Err(err()),
+            };
+            let script = Script { instructions, mode };
+            script.validate()?;
+            return Ok(script);
+        }
+        Err(ScriptError::MissingMode)
+    }
+
+    pub fn validate(&self) -> Result<(), ScriptError> {
+        if self.instructions.len() > MAX_INSTRUCTIONS {
+            return Err(ScriptError::TooLong(self.instructions.len()));
+        }
+        for (i, ins) in self.instructions.iter().enumerate() {
+            if let Register::Sensor(_) = ins.y {
+                return Err(ScriptError::ReadOnly {
+                    line: i + 1,
+                    register: ins.y,
+                });
+            }
+            if let Register::Sensor(s) = ins.x {
+                if s as usize >= self.mode.range() {
+                    return Err(ScriptError::OutOfRange {
+                        line: i + 1,
+                        register: ins.x,
+                    });
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Source as the springdroid expects it, one instruction per line.
+    pub fn to_springcode(&self) -> String {
+        let mut code = String::new();
+        for ins in &self.instructions {
+            code += &format!("{}\n", ins);
+        }
+        code + &format!("{}\n", self.mode)
+    }
+
+    pub fn evaluate(&self, sensors: u16) -> bool {
+        let mut t = false;
+        let mut j = false;
+        for ins in &self.instructions {
+            let x = match ins.x {
+                Register::Sensor(s) => sensors & (1 << s) != 0,
+                Register::Temp => t,
+                Register::Jump => j,
+            };
+            let y = match ins.y {
+                Register::Temp => &mut t,
+                Register::Jump => &mut j,
+                Register::Sensor(_) => unreachable!("sensors are read-only"),
+            };
+            *y = match ins.op {
+                Op::And => x && *y,
+                Op::Or => x || *y,
+                Op::Not => !x,
+            };
+        }
+        j
+    }
+
+    /// Walks the droid across `hull` and returns the hole it falls into, if
+    /// any.
+    pub fn simulate(&self, hull: &Hull) -> Result<(), usize> {
+        hull.walk(self.mode, |sensors| self.evaluate(sensors))
+    }
+}
+
+/// A row of hull tiles as the droid reports it, `#` for ground and `.` for a
+/// hole. Everything past the end counts as ground.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Hull {
+    tiles: Vec<bool>,
+}
+
+impl Hull {
+    pub fn parse(row: &str) -> Self {
+        Self {
+            tiles: row.trim().chars().map(|c| c != '.').collect(),
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.tiles.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.tiles.is_empty()
+    }
+
+    pub fn is_ground(&self, x: usize) -> bool {
+        *self.tiles.get(x).unwrap_or(&true)
+    }
+
+    pub fn sensors(&self, x: usize, mode: Mode) -> u16 {
+        (0..mode.range())
+            .filter(|&i| self.is_ground(x + 1 + i))
+            .fold(0, |mask, i| mask | 1 << i)
+    }
+
+    /// Moves the droid from tile 0 until it clears the row, asking `jump`
+    /// what to do at every tile it lands on.
+    pub fn walk(&self, mode: Mode, mut jump: impl FnMut(u16) -> bool) -> Result<(), usize> {
+        let mut x = 0;
+        while x < self.len() {
+            x += if jump(self.sensors(x, mode)) {
+                mode.jump()
+            } else {
+                1
+            };
+            if !self.is_ground(x) {
+                return Err(x);
+            }
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for Hull {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for &ground in &self.tiles {
+            write!(f, "{}", if ground { '#' } else { '.' })?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Jumps whenever one of the next three tiles is a hole and the landing
+    /// tile is ground.
+    const WALK_SCRIPT: &str = "\
+NOT A J
+NOT B T
+OR T J
+NOT C T
+OR T J
+AND D J
+WALK
+";
+
+    /// The same, but only if the droid can walk on or jump again from where
+    /// it lands.
+    const RUN_SCRIPT: &str = "\
+NOT A J
+NOT B T
+OR T J
+NOT C T
+OR T J
+AND D J
+NOT E T
+NOT T T
+OR H T
+AND T J
+RUN
+";
+
+    #[test]
+    fn parses_known_scripts() {
+        let walk = Script::parse(WALK_SCRIPT).unwrap();
+        assert_eq!(walk.mode, Mode::Walk);
+        assert_eq!(walk.instructions.len(), 6);
+        assert_eq!(
+            walk.instructions[1],
+            Instruction {
+                op: Op::Not,
+                x: Register::Sensor(1),
+                y: Register::Temp,
+            }
+        );
+        assert_eq!(walk.to_springcode(), WALK_SCRIPT);
+
+        let run = Script::parse(RUN_SCRIPT).unwrap();
+        assert_eq!(run.mode, Mode::Run);
+        assert_eq!(run.instructions.len(), 10);
+        assert_eq!(run.to_springcode(), RUN_SCRIPT);
+
+        assert_eq!(
+            Script::parse("  \nNOT A J\n\n  WALK  \n")
+                .unwrap()
+                .instructions
+                .len(),
+            1
+        );
+    }
+
+    #[test]
+    fn rejects_bad_scripts() {
+        assert_eq!(
+            Script::parse("NOT A J\nJUMP A J\nWALK"),
+            Err(ScriptError::Parse {
+                line: 2,
+                text: "JUMP A J".to_string(),
+            })
+        );
+        assert_eq!(
+            Script::parse("NOT X J\nWALK"),
+            Err(ScriptError::Parse {
+                line: 1,
+                text: "NOT X J".to_string(),
+            })
+        );
+        assert_eq!(Script::parse("NOT A J\n"), Err(ScriptError::MissingMode));
+        assert_eq!(
+            Script::parse("NOT A B\nWALK"),
+            Err(ScriptError::ReadOnly {
+                line: 1,
+                register: Register::Sensor(1),
+            })
+        );
+
+        let too_long = "NOT A J\n".repeat(MAX_INSTRUCTIONS + 1) + "WALK";
+        assert_eq!(
+            Script::parse(&too_long),
+            Err(ScriptError::TooLong(MAX_INSTRUCTIONS + 1))
+        );
+        let longest = "NOT A J\n".repeat(MAX_INSTRUCTIONS) + "WALK";
+        assert!(Script::parse(&longest).is_ok());
+
+        assert_eq!(
+            Script::parse("NOT A J\nOR E J\nWALK"),
+            Err(ScriptError::OutOfRange {
+                line: 2,
+                register: Register::Sensor(4),
+            })
+        );
+        assert!(Script::parse("NOT A J\nOR I J\nRUN").is_ok());
+    }
+
+    #[test]
+    fn simulates_against_hulls() {
+        let walk = Script::parse(WALK_SCRIPT).unwrap();
+        for row in [
+            "#####.###########",
+            "#####..#.########",
+            "#####...#########",
+        ] {
+            assert_eq!(walk.simulate(&Hull::parse(row)), Ok(()), "{}", row);
+        }
+        // Jumping at the first sign of a hole lands just before the second,
+        // with the hole after that blocking another jump.
+        assert_eq!(walk.simulate(&Hull::parse("#####.#.##.######")), Err(7));
+
+        let run = Script::parse(RUN_SCRIPT).unwrap();
+        for row in [
+            "#####.###########",
+            "#####...#########",
+            "#####.#.##.######",
+            "#####.##.##.#.###",
+        ] {
+            assert_eq!(run.simulate(&Hull::parse(row)), Ok(()), "{}", row);
+        }
+
+        // Never jumping walks straight into the first hole.
+        let never = Script::parse("WALK").unwrap();
+        assert_eq!(never.simulate(&Hull::parse("#####.####")), Err(5));
+        assert_eq!(never.simulate(&Hull::parse("##########")), Ok(()));
+    }
+}
@@ -0,0 +1,228 @@
+use std::collections::HashMap;
+
+use super::springscript::{Hull, Instruction, Mode, Op, Register, Script, MAX_INSTRUCTIONS};
+
+const MAX_LITERALS: usize = 3;
+const MAX_TERMS: usize = 4;
+
+/// A conjunction of sensor readings: every sensor in `ground` must see
+/// ground and every sensor in `holes` must see a hole.
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct Term {
+    ground: u16,
+    holes: u16,
+}
+
+impl Term {
+    fn matches(self, sensors: u16) -> bool {
+        sensors & self.ground == self.ground && sensors & self.holes == 0
+    }
+}
+
+/// Finds a script for `mode` that gets the droid across every hull in
+/// `hulls`. Jump decisions are assigned to sensor readings as the droid
+/// meets them, preferring to keep walking, and each consistent assignment
+/// is compiled into a sum of short terms until one fits in 15 instructions.
+pub fn synthesize(hulls: &[Hull], mode: Mode) -> Option<Script> {
+    let mut labels = HashMap::new();
+    label(hulls, 0, 0, mode, &mut labels)
+}
+
+fn label(
+    hulls: &[Hull],
+    hull: usize,
+    x: usize,
+    mode: Mode,
+    labels: &mut HashMap<u16, bool>,
+) -> Option<Script> {
+    let Some(current) = hulls.get(hull) else {
+        return fit(labels, mode).filter(|script| hulls.iter().all(|h| script.simulate(h).is_ok()));
+    };
+    if x >= current.len() {
+        return label(hulls, hull + 1, 0, mode, labels);
+    }
+
+    let sensors = current.sensors(x, mode);
+    let choices = match labels.get(&sensors) {
+        Some(&jump) => vec![(jump, false)],
+        None => vec![(false, true), (true, true)],
+    };
+    for (jump, fresh) in choices {
+        let next = x + if jump { mode.jump() } else { 1 };
+        if !current.is_ground(next) {
+            continue;
+        }
+        if fresh {
+            labels.insert(sensors, jump);
+        }
+        let script = label(hulls, hull, next, mode, labels);
+        if fresh {
+            labels.remove(&sensors);
+        }
+        if script.is_some() {
+            return script;
+        }
+    }
+    None
+}
+
+/// Smallest sum of terms that is true on every jump reading and false on
+/// every walk reading, compiled into a script short enough for the droid.
+fn fit(labels: &HashMap<u16, bool>, mode: Mode) -> Option<Script> {
+    let jumps = labels
+        .iter()
+        .filter(|(_, &j)| j)
+        .map(|(&s, _)| s)
+        .collect::<Vec<_>>();
+    let walks = labels
+        .iter()
+        .filter(|(_, &j)| !j)
+        .map(|(&s, _)| s)
+        .collect::<Vec<_>>();
+    (0..=MAX_TERMS).find_map(|terms| cover(&jumps, &walks, mode, terms, &mut vec![]))
+}
+
+fn cover(
+    jumps: &[u16],
+    walks: &[u16],
+    mode: Mode,
+    terms_left: usize,
+    terms: &mut Vec<Term>,
+) -> Option<Script> {
+    let Some(&uncovered) = jumps.iter().find(|&&s| !terms.iter().any(|t| t.matches(s))) else {
+        return Some(compile(terms, mode)).filter(|s| s.instructions.len() <= MAX_INSTRUCTIONS);
+    };
+    if terms_left == 0 {
+        return None;
+    }
+    for term in candidates(uncovered, mode) {
+        if walks.iter().any(|&s| term.matches(s)) {
+            continue;
+        }
+        terms.push(term);
+        let script = cover(jumps, walks, mode, terms_left - 1, terms);
+        terms.pop();
+        if script.is_some() {
+            return script;
+        }
+    }
+    None
+}
+
+/// Every term of up to three sensors that holds for `sensors`, shortest
+/// first.
+fn candidates(sensors: u16, mode: Mode) -> Vec<Term> {
+    let mut subsets = (1u16..1 << mode.range())
+        .filter(|s| s.count_ones() as usize <= MAX_LITERALS)
+        .collect::<Vec<_>>();
+    subsets.sort_by_key(|s| s.count_ones());
+    subsets
+        .into_iter()
+        .map(|s| Term {
+            ground: s & sensors,
+            holes: s & !sensors,
+        })
+        .collect()
+}
+
+/// Compiles the first term into `J` and ORs every later one in through `T`.
+/// A term with holes is built as `!(hole | hole ..) & ground & ground ..`.
+fn compile(terms: &[Term], mode: Mode) -> Script {
+    let mut instructions = vec![];
+    let mut temp_clear = true;
+    for (i, term) in terms.iter().enumerate() {
+        let target = if i == 0 {
+            Register::Jump
+        } else {
+            Register::Temp
+        };
+        let clear = i == 0 || temp_clear;
+        let sensors = |mask: u16| {
+            (0..16u8)
+                .filter(move |b| mask & (1 << b) != 0)
+                .map(Register::Sensor)
+        };
+        let mut emit = |op, x| instructions.push(Instruction { op, x, y: target });
+
+        let (first, rest_op, rest, tail) = match sensors(term.holes).next() {
+            Some(first) => (first, Op::Or, term.holes, true),
+            None => (
+                sensors(term.ground).next().unwrap(),
+                Op::And,
+                term.ground,
+                false,
+            ),
+        };
+        if clear {
+            emit(Op::Or, first);
+        } else {
+            emit(Op::Not, first);
+            emit(Op::Not, target);
+        }
+        for x in sensors(rest).skip(1) {
+            emit(rest_op, x);
+        }
+        if tail {
+            emit(Op::Not, target);
+            for x in sensors(term.ground) {
+                emit(Op::And, x);
+            }
+        }
+
+        if i > 0 {
+            temp_clear = false;
+            instructions.push(Instruction {
+                op: Op::Or,
+                x: Register::Temp,
+                y: Register::Jump,
+            });
+        }
+    }
+    Script { instructions, mode }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn hulls(rows: &[&str]) -> Vec<Hull> {
+        rows.iter().map(|r| Hull::parse(r)).collect()
+    }
+
+    #[test]
+    fn walks_simple_hulls() {
+        let hulls = hulls(&[
+            "#####.###########",
+            "#####..#.########",
+            "#####...#########",
+        ]);
+        let script = synthesize(&hulls, Mode::Walk).unwrap();
+        assert_eq!(script.mode, Mode::Walk);
+        assert!(script.instructions.len() <= MAX_INSTRUCTIONS);
+        assert_eq!(script.validate(), Ok(()));
+        for hull in &hulls {
+            assert_eq!(script.simulate(hull), Ok(()), "{}", hull);
+        }
+    }
+
+    #[test]
+    fn needs_run_for_conflicting_hulls() {
+        // The two hulls need opposite decisions on a reading they share in
+        // WALK range, which only the longer RUN range tells apart.
+        let hulls = hulls(&["#####.#.##.######", "#####.##.##.#.###"]);
+        assert_eq!(synthesize(&hulls, Mode::Walk), None);
+
+        let script = synthesize(&hulls, Mode::Run).unwrap();
+        assert_eq!(script.mode, Mode::Run);
+        assert_eq!(script.validate(), Ok(()));
+        for hull in &hulls {
+            assert_eq!(script.simulate(hull), Ok(()), "{}", hull);
+        }
+    }
+
+    #[test]
+    fn never_jumps_on_solid_ground() {
+        let script = synthesize(&hulls(&["#########"]), Mode::Walk).unwrap();
+        assert!(script.instructions.is_empty());
+    }
+}
@@ -1,6 +1,7 @@
 #![allow(dead_code)]
 mod part1;
 mod part2;
+mod shuffle;
 
 pub fn run() {
     println!("====================Day 22====================");
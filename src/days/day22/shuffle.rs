@@ -0,0 +1,322 @@
+use std::fmt;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Technique {
+    NewStack,
+    Cut(i64),
+    Increment(u64),
+}
+
+#[derive(Debug, PartialEq)]
+pub enum ShuffleError {
+    Parse(String),
+    EmptyDeck,
+    /// Dealing with this increment would put two cards in the same spot.
+    SharedFactor {
+        increment: u64,
+        deck_size: u64,
+    },
+}
+
+impl fmt::Display for ShuffleError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ShuffleError::Parse(line) => write!(f, "cannot parse technique {:?}", line),
+            ShuffleError::EmptyDeck => write!(f, "deck must have at least one card"),
+            ShuffleError::SharedFactor {
+                increment,
+                deck_size,
+            } => write!(
+                f,
+                "increment {} shares a factor with deck size {}",
+                increment, deck_size
+            ),
+        }
+    }
+}
+
+impl Technique {
+    pub fn parse(line: &str) -> Result<Self, ShuffleError> {
+        let err = || ShuffleError::Parse(line.to_string());
+        let words = line.split_whitespace().collect::<Vec<_>>();
+        match words[..] {
+            ["deal", "into", "new", "stack"] => Ok(Technique::NewStack),
+            ["cut", n] => n.parse().map(Technique::Cut).map_err(|_| err()),
+            ["deal", "with", "increment", n] => {
+                n.parse().map(Technique::Increment).map_err(|_| err())
+            }
+            _ => Err(err()),
+        }
+    }
+
+    pub fn parse_all(input: &str) -> Result<Vec<Self>, ShuffleError> {
+        input
+            .lines()
+            .filter(|l| !l.trim().is_empty())
+            .map(Self::parse)
+            .collect()
+    }
+}
+
+/// A shuffle of a deck of `size` cards as the affine map that sends the card
+/// at position `p` to position `a * p + b (mod size)`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Shuffle {
+    a: u64,
+    b: u64,
+    size: u64,
+}
+
+impl Shuffle {
+    pub fn identity(size: u64) -> Result<Self, ShuffleError> {
+        if size == 0 {
+            return Err(ShuffleError::EmptyDeck);
+        }
+        Ok(Self {
+            a: 1 % size,
+            b: 0,
+            size,
+        })
+    }
+
+    pub fn technique(technique: Technique, size: u64) -> Result<Self, ShuffleError> {
+        let id = Self::identity(size)?;
+        let (a, b) = match technique {
+            Technique::NewStack => (size - 1, size - 1),
+            Technique::Cut(n) => (1, (-(n as i128)).rem_euclid(size as i128) as u64),
+            Technique::Increment(n) => {
+                if gcd(n, size) != 1 {
+                    return Err(ShuffleError::SharedFactor {
+                        increment: n,
+                        deck_size: size,
+                    });
+                }
+                (n, 0)
+            }
+        };
+        Ok(Self {
+            a: a % size,
+            b: b % size,
+            ..id
+        })
+    }
+
+    pub fn from_techniques(techniques: &[Technique], size: u64) -> Result<Self, ShuffleError> {
+        techniques
+            .iter()
+            .try_fold(Self::identity(size)?, |shuffle, &t| {
+                Ok(shuffle.then(&Self::technique(t, size)?))
+            })
+    }
+
+    pub fn parse(input: &str, size: u64) -> Result<Self, ShuffleError> {
+        Self::from_techniques(&Technique::parse_all(input)?, size)
+    }
+
+    pub fn from_file(path: &str, size: u64) -> Result<Self, ShuffleError> {
+        Self::parse(
+            &std::fs::read_to_string(path).expect("File should be there"),
+            size,
+        )
+    }
+
+    pub fn size(&self) -> u64 {
+        self.size
+    }
+
+    /// Coefficients `(a, b)` of the position map.
+    pub fn coefficients(&self) -> (u64, u64) {
+        (self.a, self.b)
+    }
+
+    /// Shuffling by `self` and then by `other`.
+    pub fn then(&self, other: &Self) -> Self {
+        assert_eq!(self.size, other.size, "shuffles must share a deck size");
+        Self {
+            a: mul_mod(other.a, self.a, self.size),
+            b: add_mod(mul_mod(other.a, self.b, self.size), other.b, self.size),
+            size: self.size,
+        }
+    }
+
+    /// The shuffle that puts every card back where it started.
+    pub fn inverse(&self) -> Self {
+        let a = mod_inv(self.a, self.size).expect("shuffles are always invertible");
+        Self {
+            a,
+            b: mul_mod(a, self.size - self.b, self.size),
+            size: self.size,
+        }
+    }
+
+    /// The shuffle repeated `times` times in a row.
+    pub fn pow(&self, mut times: u64) -> Self {
+        let mut result = Self {
+            a: 1 % self.size,
+            b: 0,
+            size: self.size,
+        };
+        let mut base = *self;
+        while times > 0 {
+            if times & 1 == 1 {
+                result = result.then(&base);
+            }
+            base = base.then(&base);
+            times >>= 1;
+        }
+        result
+    }
+
+    /// Where the card that started at `card` ends up.
+    pub fn position_of(&self, card: u64) -> u64 {
+        add_mod(
+            mul_mod(self.a, card % self.size, self.size),
+            self.b,
+            self.size,
+        )
+    }
+
+    /// Which card ends up at `position`.
+    pub fn card_at(&self, position: u64) -> u64 {
+        self.inverse().position_of(position)
+    }
+}
+
+/// Deals a factory-order deck of `size` cards through every technique the
+/// slow way, for checking [`Shuffle`] on small decks.
+pub fn brute_force(techniques: &[Technique], size: usize, times: usize) -> Vec<u64> {
+    let mut deck = (0..size as u64).collect::<Vec<_>>();
+    for _ in 0..times {
+        for &technique in techniques {
+            deck = match technique {
+                Technique::NewStack => deck.into_iter().rev().collect(),
+                Technique::Cut(n) => {
+                    let n = n.rem_euclid(size as i64) as usize;
+                    deck[n..].iter().chain(&deck[..n]).copied().collect()
+                }
+                Technique::Increment(n) => {
+                    let mut dealt = vec![0; size];
+                    for (i, card) in deck.into_iter().enumerate() {
+                        dealt[(i as u128 * n as u128 % size as u128) as usize] = card;
+                    }
+                    dealt
+                }
+            };
+        }
+    }
+    deck
+}
+
+/// Whether the algebra agrees with a brute-force deal of `size` cards
+/// repeated `times` times, both forwards and backwards, for every card.
+pub fn cross_check(
+    techniques: &[Technique],
+    size: usize,
+    times: usize,
+) -> Result<bool, ShuffleError> {
+    let shuffle = Shuffle::from_techniques(techniques, size as u64)?.pow(times as u64);
+    let deck = brute_force(techniques, size, times);
+    Ok(deck.iter().enumerate().all(|(position, &card)| {
+        shuffle.position_of(card) == position as u64 && shuffle.card_at(position as u64) == card
+    }))
+}
+
+fn mul_mod(a: u64, b: u64, m: u64) -> u64 {
+    (a as u128 * b as u128 % m as u128) as u64
+}
+
+fn add_mod(a: u64, b: u64, m: u64) -> u64 {
+    ((a as u128 + b as u128) % m as u128) as u64
+}
+
+fn gcd(a: u64, b: u64) -> u64 {
+    if b == 0 {
+        a
+    } else {
+        gcd(b, a % b)
+    }
+}
+
+/// Inverse of `a` modulo `m`, if they are coprime.
+fn mod_inv(a: u64, m: u64) -> Option<u64> {
+    let (mut r0, mut r1) = (m as i128, (a % m) as i128);
+    let (mut t0, mut t1) = (0i128, 1i128);
+    while r1 != 0 {
+        let q = r0 / r1;
+        (r0, r1) = (r1, r0 - q * r1);
+        (t0, t1) = (t1, t0 - q * t1);
+    }
+    (r0 == 1).then(|| t0.rem_euclid(m as i128) as u64)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLES: [(&str, [u64; 10]); 4] = [
+        (
+            "deal with increment 7\ndeal into new stack\ndeal into new stack",
+            [0, 3, 6, 9, 2, 5, 8, 1, 4, 7],
+        ),
+        (
+            "cut 6\ndeal with increment 7\ndeal into new stack",
+            [3, 0, 7, 4, 1, 8, 5, 2, 9, 6],
+        ),
+        (
+            "deal with increment 7\ndeal with increment 9\ncut -2",
+            [6, 3, 0, 7, 4, 1, 8, 5, 2, 9],
+        ),
+        (
+            "deal into new stack\ncut -2\ndeal with increment 7\ncut 8\ncut -4\n\
+             deal with increment 7\ncut 3\ndeal with increment 9\ndeal with increment 3\ncut -1",
+            [9, 2, 5, 8, 1, 4, 7, 0, 3, 6],
+        ),
+    ];
+
+    #[test]
+    fn examples() {
+        for (input, expected) in EXAMPLES {
+            let techniques = Technique::parse_all(input).unwrap();
+            assert_eq!(brute_force(&techniques, 10, 1), expected);
+            let shuffle = Shuffle::from_techniques(&techniques, 10).unwrap();
+            let deck = (0..10).map(|p| shuffle.card_at(p)).collect::<Vec<_>>();
+            assert_eq!(deck, expected);
+        }
+    }
+
+    #[test]
+    fn matches_brute_force() {
+        for (input, _) in EXAMPLES {
+            let techniques = Technique::parse_all(input).unwrap();
+            for size in [1, 2, 4, 5, 8, 10, 11, 13, 16, 17, 19, 20, 23] {
+                for times in 0..6 {
+                    assert_eq!(
+                        cross_check(&techniques, size, times),
+                        Ok(true),
+                        "{:?} on {} cards, {} times",
+                        input,
+                        size,
+                        times
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn huge_decks() {
+        let techniques = Technique::parse_all(EXAMPLES[3].0).unwrap();
+        for size in [u64::MAX - 58, 1 << 63, 119315717514047] {
+            let shuffle = Shuffle::from_techniques(&techniques, size)
+                .unwrap()
+                .pow(12345);
+            for card in [0, 1, 2020, size - 1] {
+                assert_eq!(shuffle.card_at(shuffle.position_of(card)), card);
+            }
+            assert_eq!(
+                shuffle.then(&shuffle.inverse()),
+                Shuffle::identity(size).unwrap()
+            );
+        }
+    }
+}
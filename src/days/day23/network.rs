@@ -0,0 +1,82 @@
+use crate::intcode::{read_intcode, Program, Status};
+
+/// Steps a NIC may take per turn, so one busy NIC cannot stall the rest.
+const SLICE: u64 = 10_000;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Packet {
+    pub dest: i64,
+    pub x: i64,
+    pub y: i64,
+}
+
+pub struct Network {
+    nics: Vec<Program>,
+    outboxes: Vec<Vec<i64>>,
+}
+
+impl Network {
+    pub fn new(intcode: Vec<i64>, size: usize) -> Self {
+        Self {
+            nics: (0..size)
+                .map(|i| Program::new(intcode.clone(), vec![i as i64]))
+                .collect(),
+            outboxes: vec![vec![]; size],
+        }
+    }
+
+    pub fn from_file(path: &str, size: usize) -> Self {
+        Self::new(read_intcode(path), size)
+    }
+
+    /// Instructions executed by every NIC together.
+    pub fn steps(&self) -> u64 {
+        self.nics.iter().map(|nic| nic.steps()).sum()
+    }
+
+    pub fn send(&mut self, packet: Packet) {
+        self.nics[packet.dest as usize]
+            .get_pending_inputs()
+            .extend([packet.x, packet.y]);
+    }
+
+    /// Gives every NIC one turn and delivers the packets they send. Returns
+    /// the packets addressed outside the network and whether the network
+    /// was idle, with no packets sent and no NIC still busy.
+    pub fn round(&mut self) -> (Vec<Packet>, bool) {
+        let mut outside = vec![];
+        let mut idle = true;
+        for i in 0..self.nics.len() {
+            let nic = &mut self.nics[i];
+            if *nic.status() == Status::Halted {
+                continue;
+            }
+            if *nic.status() == Status::WaitingForInput && nic.get_pending_inputs().is_empty() {
+                nic.get_pending_inputs().push(-1);
+            }
+            nic.set_budget(Some(SLICE));
+            nic.run();
+            if *nic.status() == Status::BudgetExhausted {
+                idle = false;
+            }
+            self.outboxes[i].extend(nic.read_all_outputs());
+
+            let complete = self.outboxes[i].len() / 3 * 3;
+            let sent = self.outboxes[i].drain(..complete).collect::<Vec<_>>();
+            for chunk in sent.chunks_exact(3) {
+                idle = false;
+                let packet = Packet {
+                    dest: chunk[0],
+                    x: chunk[1],
+                    y: chunk[2],
+                };
+                if (0..self.nics.len() as i64).contains(&packet.dest) {
+                    self.send(packet);
+                } else {
+                    outside.push(packet);
+                }
+            }
+        }
+        (outside, idle)
+    }
+}
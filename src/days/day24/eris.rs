@@ -0,0 +1,338 @@
+use std::collections::HashSet;
+use std::fmt;
+
+const SIZE: usize = 5;
+const CELLS: usize = SIZE * SIZE;
+const CENTER: usize = CELLS / 2;
+const FULL: u32 = (1 << CELLS) - 1;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Topology {
+    Flat,
+    /// Every level's middle tile holds another whole grid, and every level
+    /// sits in the middle tile of the one outside it.
+    Recursive,
+}
+
+/// For every cell, the bits of its neighbours on the same level, on the
+/// level outside it and on the level inside it.
+#[derive(Clone, Debug)]
+struct Masks {
+    same: [u32; CELLS],
+    outer: [u32; CELLS],
+    inner: [u32; CELLS],
+    /// Cells that can see into an inner level.
+    bordering: Vec<usize>,
+    /// Outer-level cells paired with the edge of this level they touch.
+    entries: [(u32, u32); 4],
+    first_col: u32,
+    last_col: u32,
+    live: u32,
+}
+
+fn bit(r: usize, c: usize) -> u32 {
+    1 << (r * SIZE + c)
+}
+
+fn row(r: usize) -> u32 {
+    (0..SIZE).fold(0, |m, c| m | bit(r, c))
+}
+
+fn col(c: usize) -> u32 {
+    (0..SIZE).fold(0, |m, r| m | bit(r, c))
+}
+
+impl Masks {
+    fn new(topology: Topology) -> Self {
+        let recursive = topology == Topology::Recursive;
+        let mut masks = Self {
+            same: [0; CELLS],
+            outer: [0; CELLS],
+            inner: [0; CELLS],
+            bordering: vec![],
+            entries: [(0, 0); 4],
+            first_col: col(0),
+            last_col: col(SIZE - 1),
+            live: if recursive {
+                FULL & !(1 << CENTER)
+            } else {
+                FULL
+            },
+        };
+        let mid = SIZE / 2;
+        if recursive {
+            masks.entries = [
+                (bit(mid - 1, mid), row(0)),
+                (bit(mid + 1, mid), row(SIZE - 1)),
+                (bit(mid, mid - 1), col(0)),
+                (bit(mid, mid + 1), col(SIZE - 1)),
+            ];
+        }
+
+        for r in 0..SIZE {
+            for c in 0..SIZE {
+                let cell = r * SIZE + c;
+                if recursive && cell == CENTER {
+                    continue;
+                }
+                let mut same = 0;
+                if r > 0 {
+                    same |= bit(r - 1, c);
+                }
+                if r + 1 < SIZE {
+                    same |= bit(r + 1, c);
+                }
+                if c > 0 {
+                    same |= bit(r, c - 1);
+                }
+                if c + 1 < SIZE {
+                    same |= bit(r, c + 1);
+                }
+                if !recursive {
+                    masks.same[cell] = same;
+                    continue;
+                }
+                masks.same[cell] = same & !(1 << CENTER);
+
+                let mut outer = 0;
+                if r == 0 {
+                    outer |= bit(mid - 1, mid);
+                }
+                if r == SIZE - 1 {
+                    outer |= bit(mid + 1, mid);
+                }
+                if c == 0 {
+                    outer |= bit(mid, mid - 1);
+                }
+                if c == SIZE - 1 {
+                    outer |= bit(mid, mid + 1);
+                }
+                masks.outer[cell] = outer;
+
+                masks.inner[cell] = match (r, c) {
+                    (r, c) if r == mid - 1 && c == mid => row(0),
+                    (r, c) if r == mid + 1 && c == mid => row(SIZE - 1),
+                    (r, c) if r == mid && c == mid - 1 => col(0),
+                    (r, c) if r == mid && c == mid + 1 => col(SIZE - 1),
+                    _ => 0,
+                };
+                if masks.inner[cell] != 0 {
+                    masks.bordering.push(cell);
+                }
+            }
+        }
+        masks
+    }
+
+    /// Next state of a whole level. The four ways in from a neighbouring
+    /// cell are added bitwise, one bit per cell, for every cell at once;
+    /// the few cells that border an inner level can see up to five bugs
+    /// there and are counted one by one afterwards.
+    fn next(&self, outer: u32, level: u32, inner: u32) -> u32 {
+        let mut count = Count::default();
+        count.add((level << SIZE) & FULL);
+        count.add(level >> SIZE);
+        count.add((level << 1) & !self.first_col);
+        count.add((level >> 1) & !self.last_col);
+        for (from, to) in self.entries {
+            if outer & from != 0 {
+                count.add(to);
+            }
+        }
+        let mut next = (count.one | (count.two & !level)) & self.live;
+
+        for &cell in &self.bordering {
+            let adjacent = (level & self.same[cell]).count_ones()
+                + (outer & self.outer[cell]).count_ones()
+                + (inner & self.inner[cell]).count_ones();
+            let bug = level & (1 << cell) != 0;
+            next &= !(1 << cell);
+            if adjacent == 1 || (!bug && adjacent == 2) {
+                next |= 1 << cell;
+            }
+        }
+        next
+    }
+}
+
+/// Per-cell neighbour counts as bit planes, saturating at three.
+#[derive(Default)]
+struct Count {
+    one: u32,
+    two: u32,
+    many: u32,
+}
+
+impl Count {
+    fn add(&mut self, cells: u32) {
+        let none = !(self.one | self.two | self.many);
+        self.many |= self.two & cells;
+        self.two = (self.two & !cells) | (self.one & cells);
+        self.one = (self.one & !cells) | (none & cells);
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub enum ErisError {
+    RowCount(usize),
+    RowLength { row: usize, len: usize },
+    InvalidCell(char),
+}
+
+impl fmt::Display for ErisError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ErisError::RowCount(n) => write!(f, "expected {} rows, got {}", SIZE, n),
+            ErisError::RowLength { row, len } => {
+                write!(f, "row {} has {} cells instead of {}", row + 1, len, SIZE)
+            }
+            ErisError::InvalidCell(c) => write!(f, "invalid cell {:?}", c),
+        }
+    }
+}
+
+/// Bugs on Eris, one 25-bit board per level with bit `r * 5 + c` set for a
+/// bug. Levels are stored from the outermost inwards, and empty levels at
+/// either end are dropped after every minute.
+#[derive(Clone, Debug)]
+pub struct Eris {
+    topology: Topology,
+    masks: Masks,
+    levels: Vec<u32>,
+    /// Depth of `levels[0]`; depth 0 is the scanned level and inner levels
+    /// are positive.
+    top: i32,
+    minute: usize,
+}
+
+impl Eris {
+    pub fn new(level: u32, topology: Topology) -> Self {
+        let masks = Masks::new(topology);
+        Self {
+            topology,
+            levels: vec![level & masks.live],
+            masks,
+            top: 0,
+            minute: 0,
+        }
+    }
+
+    /// Reads a 5x5 scan of `#` bugs and `.` empty cells. A `?` is also taken
+    /// as empty, so rendered recursive levels read back in.
+    pub fn parse(input: &str, topology: Topology) -> Result<Self, ErisError> {
+        let rows = input.trim().lines().map(str::trim).collect::<Vec<_>>();
+        if rows.len() != SIZE {
+            return Err(ErisError::RowCount(rows.len()));
+        }
+        let mut level = 0u32;
+        for (r, row) in rows.iter().enumerate() {
+            let len = row.chars().count();
+            if len != SIZE {
+                return Err(ErisError::RowLength { row: r, len });
+            }
+            for (c, cell) in row.chars().enumerate() {
+                match cell {
+                    '#' => level |= 1 << (r * SIZE + c),
+                    '.' | '?' => {}
+                    _ => return Err(ErisError::InvalidCell(cell)),
+                }
+            }
+        }
+        Ok(Self::new(level, topology))
+    }
+
+    pub fn from_file(path: &str, topology: Topology) -> Result<Self, ErisError> {
+        Self::parse(
+            &std::fs::read_to_string(path).expect("File should be there"),
+            topology,
+        )
+    }
+
+    pub fn minute(&self) -> usize {
+        self.minute
+    }
+
+    /// Every non-empty level with its depth, outermost first.
+    pub fn levels(&self) -> impl Iterator<Item = (i32, u32)> + '_ {
+        (self.top..).zip(self.levels.iter().copied())
+    }
+
+    pub fn level(&self, depth: i32) -> u32 {
+        usize::try_from(depth - self.top)
+            .ok()
+            .and_then(|i| self.levels.get(i).copied())
+            .unwrap_or(0)
+    }
+
+    pub fn step(&mut self) {
+        let at = |i: isize| {
+            usize::try_from(i)
+                .ok()
+                .and_then(|i| self.levels.get(i).copied())
+                .unwrap_or(0)
+        };
+        let (first, last) = match self.topology {
+            Topology::Flat => (0, 0),
+            Topology::Recursive => (-1, self.levels.len() as isize),
+        };
+        let mut levels = (first..=last)
+            .map(|i| self.masks.next(at(i - 1), at(i), at(i + 1)))
+            .collect::<Vec<_>>();
+        self.top += first as i32;
+
+        let end = levels.iter().rposition(|&l| l != 0).map_or(1, |i| i + 1);
+        levels.truncate(end);
+        let start = levels.iter().position(|&l| l != 0).unwrap_or(0);
+        levels.drain(..start);
+        self.top += start as i32;
+
+        self.levels = levels;
+        self.minute += 1;
+    }
+
+    pub fn run(&mut self, minutes: usize) -> &mut Self {
+        for _ in 0..minutes {
+            self.step();
+        }
+        self
+    }
+
+    pub fn bug_count(&self) -> u32 {
+        self.levels.iter().map(|l| l.count_ones()).sum()
+    }
+
+    /// Biodiversity rating of the level at depth 0.
+    pub fn biodiversity(&self) -> u32 {
+        self.level(0)
+    }
+
+    /// Biodiversity of the first layout to appear twice. Only flat grids
+    /// are guaranteed to repeat, so recursive ones give `None`.
+    pub fn first_repeat(&self) -> Option<u32> {
+        if self.topology == Topology::Recursive {
+            return None;
+        }
+        let mut eris = self.clone();
+        let mut seen = HashSet::new();
+        while seen.insert(eris.biodiversity()) {
+            eris.step();
+        }
+        Some(eris.biodiversity())
+    }
+
+    pub fn render(&self, depth: i32) -> String {
+        let level = self.level(depth);
+        (0..SIZE)
+            .map(|r| {
+                (0..SIZE)
+                    .map(|c| match r * SIZE + c {
+                        CENTER if self.topology == Topology::Recursive => '?',
+                        cell if level & (1 << cell) != 0 => '#',
+                        _ => '.',
+                    })
+                    .collect::<String>()
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+}
@@ -0,0 +1,304 @@
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::fmt;
+
+use super::room::{Direction, Room};
+use crate::intcode::ascii::{AsciiError, AsciiProgram};
+use crate::intcode::{read_intcode, Program};
+
+/// Steps the droid may take to answer a single command. Anything that needs
+/// more is stuck, like the droid holding the infinite loop.
+pub const COMMAND_BUDGET: u64 = 1_000_000;
+
+/// Items that end the run or trap the droid when picked up.
+pub const DEFAULT_DENY_LIST: [&str; 5] = [
+    "escape pod",
+    "giant electromagnet",
+    "infinite loop",
+    "molten lava",
+    "photons",
+];
+
+#[derive(Debug, PartialEq)]
+pub enum ExploreError {
+    NoRoom(String),
+    /// The droid said something the explorer does not understand.
+    Unexpected(String),
+    NoCheckpoint,
+    NoMatchingWeight,
+    Droid(AsciiError),
+}
+
+impl From<AsciiError> for ExploreError {
+    fn from(e: AsciiError) -> Self {
+        ExploreError::Droid(e)
+    }
+}
+
+impl fmt::Display for ExploreError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ExploreError::NoRoom(text) => write!(f, "no room in droid output:\n{}", text),
+            ExploreError::Unexpected(text) => write!(f, "unexpected droid reply:\n{}", text),
+            ExploreError::NoCheckpoint => write!(f, "no way past the security checkpoint"),
+            ExploreError::NoMatchingWeight => {
+                write!(f, "no combination of items has the right weight")
+            }
+            ExploreError::Droid(e) => write!(f, "{}", e),
+        }
+    }
+}
+
+/// Every room seen so far and the doors known to connect them.
+#[derive(Debug, Default)]
+pub struct ShipMap {
+    pub rooms: HashMap<String, Room>,
+    pub links: HashMap<(String, Direction), String>,
+}
+
+impl ShipMap {
+    /// Directions leading from `from` to `to` through known doors.
+    pub fn route(&self, from: &str, to: &str) -> Option<Vec<Direction>> {
+        let mut prev: HashMap<&str, (&str, Direction)> = HashMap::new();
+        let mut q = VecDeque::from([from]);
+        while let Some(room) = q.pop_front() {
+            if room == to {
+                let mut route = vec![];
+                let mut current = room;
+                while let Some(&(before, dir)) = prev.get(current) {
+                    route.push(dir);
+                    current = before;
+                }
+                route.reverse();
+                return Some(route);
+            }
+            for ((start, dir), next) in &self.links {
+                if start == room && next != from && !prev.contains_key(next.as_str()) {
+                    prev.insert(next, (room, *dir));
+                    q.push_back(next);
+                }
+            }
+        }
+        None
+    }
+}
+
+#[derive(Debug)]
+pub struct Report {
+    pub password: String,
+    /// Items the droid was holding when the floor let it through.
+    pub items: Vec<String>,
+    pub map: ShipMap,
+}
+
+/// Drives the droid around the ship with no input from the player: it maps
+/// every room, picks up every item that is not on the deny-list, then works
+/// out at the security checkpoint which items weigh the right amount.
+pub struct Explorer {
+    droid: AsciiProgram,
+    deny: HashSet<String>,
+    budget: u64,
+    map: ShipMap,
+    room: String,
+    held: Vec<String>,
+    /// The checkpoint and the door onto the pressure-sensitive floor.
+    floor: Option<(String, Direction)>,
+    ending: Option<String>,
+}
+
+impl Explorer {
+    pub fn new(intcode: Vec<i64>) -> Self {
+        Self {
+            droid: AsciiProgram::new(Program::new(intcode, vec![])),
+            deny: DEFAULT_DENY_LIST.iter().map(|s| s.to_string()).collect(),
+            budget: COMMAND_BUDGET,
+            map: ShipMap::default(),
+            room: String::new(),
+            held: vec![],
+            floor: None,
+            ending: None,
+        }
+    }
+
+    pub fn from_file(path: &str) -> Self {
+        Self::new(read_intcode(path))
+    }
+
+    pub fn with_deny_list(mut self, items: &[&str]) -> Self {
+        self.deny = items.iter().map(|s| s.to_string()).collect();
+        self
+    }
+
+    pub fn with_budget(mut self, steps: u64) -> Self {
+        self.budget = steps;
+        self
+    }
+
+    fn send(&mut self, command: &str) -> Result<String, ExploreError> {
+        self.droid.send_line(command)?;
+        self.read()
+    }
+
+    fn read(&mut self) -> Result<String, ExploreError> {
+        self.droid.program_mut().set_budget(Some(self.budget));
+        Ok(self.droid.read_text()?)
+    }
+
+    fn go(&mut self, dir: Direction) -> Result<Room, ExploreError> {
+        let text = self.send(&dir.to_string())?;
+        Room::parse(&text).ok_or(ExploreError::NoRoom(text))
+    }
+
+    pub fn run(mut self) -> Result<Report, ExploreError> {
+        let text = self.read()?;
+        let start = Room::parse(&text).ok_or(ExploreError::NoRoom(text))?;
+        self.room = start.name.clone();
+        self.visit(start)?;
+        if let Some(ending) = self.ending.take() {
+            let items = self.held.clone();
+            return Ok(self.report(ending, items));
+        }
+
+        let (checkpoint, floor) = self.floor.clone().ok_or(ExploreError::NoCheckpoint)?;
+        let route = self
+            .map
+            .route(&self.room, &checkpoint)
+            .ok_or(ExploreError::NoCheckpoint)?;
+        for dir in route {
+            self.go(dir)?;
+        }
+        self.room = checkpoint;
+        let items = self.find_weight(floor)?;
+        let ending = self.ending.take().unwrap_or_default();
+        Ok(self.report(ending, items))
+    }
+
+    fn report(self, ending: String, items: Vec<String>) -> Report {
+        Report {
+            password: password(&ending).unwrap_or(ending),
+            items,
+            map: self.map,
+        }
+    }
+
+    /// Depth-first walk from `room`, returning the droid to it afterwards.
+    fn visit(&mut self, room: Room) -> Result<(), ExploreError> {
+        for item in &room.items {
+            if !self.deny.contains(item) {
+                self.take(item)?;
+            }
+        }
+        let name = room.name.clone();
+        let doors = room.doors.clone();
+        self.map.rooms.insert(name.clone(), room);
+
+        for dir in doors {
+            if self.map.links.contains_key(&(name.clone(), dir)) {
+                continue;
+            }
+            let text = self.send(&dir.to_string())?;
+            if self.droid.is_halted() {
+                self.ending = Some(text);
+                return Ok(());
+            }
+            let next = Room::parse(&text).ok_or(ExploreError::NoRoom(text))?;
+            if next.name == name {
+                self.floor = Some((name.clone(), dir));
+                continue;
+            }
+            self.map
+                .links
+                .insert((name.clone(), dir), next.name.clone());
+            self.map
+                .links
+                .insert((next.name.clone(), dir.opposite()), name.clone());
+            if !self.map.rooms.contains_key(&next.name) {
+                self.room = next.name.clone();
+                self.visit(next)?;
+                if self.ending.is_some() {
+                    return Ok(());
+                }
+            }
+            self.go(dir.opposite())?;
+            self.room = name.clone();
+        }
+        Ok(())
+    }
+
+    /// Picks up `item`, or leaves it and denies it from now on if doing so
+    /// shuts the droid down or leaves it stuck.
+    fn take(&mut self, item: &str) -> Result<(), ExploreError> {
+        let saved = self.droid.clone();
+        match self.send(&format!("take {}", item)) {
+            Err(ExploreError::Droid(AsciiError::BudgetExhausted(_))) => {}
+            Ok(_) if self.droid.is_halted() => {}
+            Ok(text) => {
+                if text.contains("You take") {
+                    self.held.push(item.to_string());
+                }
+                return Ok(());
+            }
+            Err(e) => return Err(e),
+        }
+        self.droid = saved;
+        self.deny.insert(item.to_string());
+        Ok(())
+    }
+
+    /// Tries item combinations against the floor. A floor that finds the
+    /// droid too heavy will reject every superset of what it carried, and
+    /// one that finds it too light every subset, so those are skipped.
+    fn find_weight(&mut self, floor: Direction) -> Result<Vec<String>, ExploreError> {
+        let items = self.held.clone();
+        if items.len() >= u32::BITS as usize {
+            return Err(ExploreError::NoMatchingWeight);
+        }
+        let mut carried: u32 = (1 << items.len()) - 1;
+        let mut too_heavy: Vec<u32> = vec![];
+        let mut too_light: Vec<u32> = vec![];
+
+        let mut subsets = (0..1u32 << items.len()).collect::<Vec<_>>();
+        subsets.sort_by_key(|s| s.count_ones());
+        for subset in subsets {
+            if too_heavy.iter().any(|&h| h & !subset == 0)
+                || too_light.iter().any(|&l| subset & l == subset)
+            {
+                continue;
+            }
+            for (i, item) in items.iter().enumerate() {
+                let bit = 1 << i;
+                if carried & bit != 0 && subset & bit == 0 {
+                    self.send(&format!("drop {}", item))?;
+                } else if carried & bit == 0 && subset & bit != 0 {
+                    self.send(&format!("take {}", item))?;
+                }
+            }
+            carried = subset;
+
+            let text = self.send(&floor.to_string())?;
+            if self.droid.is_halted() {
+                self.ending = Some(text);
+                return Ok(items
+                    .iter()
+                    .enumerate()
+                    .filter(|&(i, _)| subset & (1 << i) != 0)
+                    .map(|(_, item)| item.clone())
+                    .collect());
+            }
+            if text.contains("lighter than the detected value") {
+                too_heavy.push(subset);
+            } else if text.contains("heavier than the detected value") {
+                too_light.push(subset);
+            } else {
+                return Err(ExploreError::Unexpected(text));
+            }
+        }
+        Err(ExploreError::NoMatchingWeight)
+    }
+}
+
+/// The airlock code from the droid's farewell message, if there is one.
+pub fn password(text: &str) -> Option<String> {
+    text.split_whitespace()
+        .find(|w| w.len() > 1 && w.chars().all(|c| c.is_ascii_digit()))
+        .map(str::to_string)
+}
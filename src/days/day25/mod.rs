@@ -1,8 +1,20 @@
 #![allow(dead_code)]
+mod explorer;
 mod part1;
+mod room;
+mod session;
 
 pub fn run() {
     println!("====================Day 25====================");
     println!("Part 1: {}", part1::part1("inputs/day25"));
     println!("==============================================");
 }
+
+/// Plays the text adventure by hand instead of letting the droid explore.
+pub fn play() {
+    match session::play("inputs/day25", "inputs/day25_transcript") {
+        Ok(Some(password)) => println!("Password: {}", password),
+        Ok(None) => println!("Left the ship without reaching the end."),
+        Err(e) => panic!("Session failed: {}", e),
+    }
+}
@@ -0,0 +1,98 @@
+use std::fmt;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Direction {
+    North,
+    South,
+    East,
+    West,
+}
+
+impl Direction {
+    pub fn parse(word: &str) -> Option<Self> {
+        match word {
+            "north" => Some(Direction::North),
+            "south" => Some(Direction::South),
+            "east" => Some(Direction::East),
+            "west" => Some(Direction::West),
+            _ => None,
+        }
+    }
+
+    pub fn opposite(self) -> Self {
+        match self {
+            Direction::North => Direction::South,
+            Direction::South => Direction::North,
+            Direction::East => Direction::West,
+            Direction::West => Direction::East,
+        }
+    }
+}
+
+impl fmt::Display for Direction {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let word = match self {
+            Direction::North => "north",
+            Direction::South => "south",
+            Direction::East => "east",
+            Direction::West => "west",
+        };
+        write!(f, "{}", word)
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Room {
+    pub name: String,
+    pub description: String,
+    pub doors: Vec<Direction>,
+    pub items: Vec<String>,
+}
+
+impl Room {
+    /// Parses the last room description in a block of droid output. Moving
+    /// onto the pressure-sensitive floor prints two rooms, and the droid
+    /// ends up in the second one.
+    pub fn parse(text: &str) -> Option<Self> {
+        let lines = text.lines().map(str::trim).collect::<Vec<_>>();
+        let start = lines
+            .iter()
+            .rposition(|l| l.starts_with("== ") && l.ends_with(" =="))?;
+        let name = lines[start].trim_matches(|c| c == '=' || c == ' ');
+
+        let mut room = Room {
+            name: name.to_string(),
+            description: String::new(),
+            doors: vec![],
+            items: vec![],
+        };
+        let mut section = "";
+        for line in &lines[start + 1..] {
+            match (*line, line.strip_prefix("- ")) {
+                ("", _) => section = "",
+                (_, Some(entry)) if section == "Doors here lead:" => {
+                    room.doors.extend(Direction::parse(entry));
+                }
+                (_, Some(entry)) if section == "Items here:" => {
+                    room.items.push(entry.to_string());
+                }
+                (header, None) if header.ends_with(':') => section = header,
+                (text, None) if room.description.is_empty() => {
+                    room.description = text.to_string();
+                }
+                _ => {}
+            }
+        }
+        Some(room)
+    }
+}
+
+/// Items listed after `Items in your inventory:` in the reply to `inv`.
+pub fn parse_inventory(text: &str) -> Vec<String> {
+    text.lines()
+        .skip_while(|l| !l.starts_with("Items in your inventory:"))
+        .skip(1)
+        .map_while(|l| l.strip_prefix("- "))
+        .map(str::to_string)
+        .collect()
+}
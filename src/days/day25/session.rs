@@ -0,0 +1,218 @@
+use std::collections::HashMap;
+use std::io::{self, BufRead, Write};
+
+use super::explorer::password;
+
+use crate::intcode::ascii::AsciiProgram;
+use crate::intcode::{read_intcode, Program};
+
+const ALIASES: [(&str, &str); 5] = [
+    ("n", "north"),
+    ("s", "south"),
+    ("e", "east"),
+    ("w", "west"),
+    ("i", "inv"),
+];
+
+/// Transcript lines holding a command start with this; everything else is
+/// droid output.
+const PROMPT: &str = "> ";
+
+const HELP: &str = "\
+Shortcuts: n, s, e, w, i
+:history          list commands sent so far
+:undo             take back the last command
+:checkpoint NAME  remember the current state as NAME
+:restore NAME     go back to checkpoint NAME
+:quit             leave the session
+";
+
+/// The droid's state along with what it last said.
+type Snapshot = (AsciiProgram, String);
+
+/// A hands-on session with the droid. Every command and every reply is kept
+/// in a transcript, written out after each command when a path is set, and
+/// the droid's state is saved before each command so it can be taken back.
+pub struct Session {
+    droid: AsciiProgram,
+    history: Vec<String>,
+    transcript: String,
+    /// The droid's most recent reply.
+    last: String,
+    undo: Vec<Snapshot>,
+    checkpoints: HashMap<String, Snapshot>,
+    path: Option<String>,
+}
+
+impl Session {
+    pub fn new(intcode: Vec<i64>) -> Self {
+        let mut session = Self {
+            droid: AsciiProgram::new(Program::new(intcode, vec![])),
+            history: vec![],
+            transcript: String::new(),
+            last: String::new(),
+            undo: vec![],
+            checkpoints: HashMap::new(),
+            path: None,
+        };
+        session.last = session.read_reply();
+        session.transcript += &session.last;
+        session
+    }
+
+    pub fn from_file(path: &str) -> Self {
+        Self::new(read_intcode(path))
+    }
+
+    /// Saves the transcript to `path` after every command from now on.
+    pub fn with_transcript(mut self, path: &str) -> Self {
+        self.path = Some(path.to_string());
+        self
+    }
+
+    /// Runs every command in a saved transcript, leaving the droid where
+    /// that session stopped.
+    pub fn replay(&mut self, transcript: &str) -> io::Result<()> {
+        for line in transcript.lines() {
+            if let Some(command) = line.strip_prefix(PROMPT) {
+                self.command(command)?;
+            }
+        }
+        Ok(())
+    }
+
+    pub fn transcript(&self) -> &str {
+        &self.transcript
+    }
+
+    pub fn history(&self) -> &[String] {
+        &self.history
+    }
+
+    pub fn is_halted(&self) -> bool {
+        self.droid.is_halted()
+    }
+
+    /// Handles one line of player input and returns what to show them.
+    /// Session commands start with `:`; anything else goes to the droid,
+    /// in lower case since that is all it understands.
+    pub fn command(&mut self, line: &str) -> io::Result<String> {
+        let line = line.trim();
+        let line = if line.starts_with(':') {
+            line.to_string()
+        } else {
+            line.to_ascii_lowercase()
+        };
+        let line = ALIASES
+            .iter()
+            .find(|(alias, _)| *alias == line)
+            .map_or(line.as_str(), |(_, command)| command);
+        let words = line.split_whitespace().collect::<Vec<_>>();
+
+        let (reply, record) = match words[..] {
+            [] => return Ok(String::new()),
+            [":help"] => (HELP.to_string(), false),
+            [":history"] => {
+                let list = self
+                    .history
+                    .iter()
+                    .enumerate()
+                    .map(|(i, c)| format!("{:>4}  {}\n", i + 1, c))
+                    .collect();
+                (list, false)
+            }
+            [":undo"] => match self.undo.pop() {
+                Some((droid, last)) => {
+                    self.droid = droid;
+                    self.last = last;
+                    ("Took back the last command.\n".to_string(), true)
+                }
+                None => ("Nothing to undo.\n".to_string(), false),
+            },
+            [":checkpoint", name] => {
+                self.checkpoints
+                    .insert(name.to_string(), (self.droid.clone(), self.last.clone()));
+                (format!("Saved checkpoint {}.\n", name), true)
+            }
+            [":restore", name] => match self.checkpoints.get(name) {
+                Some((droid, last)) => {
+                    let droid = std::mem::replace(&mut self.droid, droid.clone());
+                    let last = std::mem::replace(&mut self.last, last.clone());
+                    self.undo.push((droid, last));
+                    (format!("Back at checkpoint {}.\n", name), true)
+                }
+                None => (format!("No checkpoint called {}.\n", name), false),
+            },
+            [command, ..] if command.starts_with(':') => {
+                (format!("Unknown session command {}.\n", command), false)
+            }
+            _ => {
+                let snapshot = (self.droid.clone(), self.last.clone());
+                if let Err(e) = self.droid.send_line(line) {
+                    return Ok(format!("{}\n", e));
+                }
+                self.undo.push(snapshot);
+                self.last = self.read_reply();
+                (self.last.clone(), true)
+            }
+        };
+
+        if record {
+            self.history.push(line.to_string());
+            self.transcript += &format!("{}{}\n", PROMPT, line);
+            if !line.starts_with(':') {
+                self.transcript += &reply;
+            }
+            if let Some(path) = &self.path {
+                std::fs::write(path, &self.transcript)?;
+            }
+        }
+        Ok(reply)
+    }
+
+    /// What the droid printed since the last command. Output it should
+    /// never produce is shown to the player rather than ending the session.
+    fn read_reply(&mut self) -> String {
+        self.droid
+            .read_text()
+            .unwrap_or_else(|e| format!("Droid error: {}\n", e))
+    }
+
+    /// Reads commands from `input` until the droid halts or the player
+    /// quits, and returns the droid's last words if it halted.
+    pub fn interact(
+        &mut self,
+        mut input: impl BufRead,
+        mut output: impl Write,
+    ) -> io::Result<Option<String>> {
+        let mut reply = self.last.clone();
+        loop {
+            write!(output, "{}", reply)?;
+            if self.is_halted() {
+                return Ok(Some(reply));
+            }
+            write!(output, "{}", PROMPT)?;
+            output.flush()?;
+
+            let mut line = String::new();
+            if input.read_line(&mut line)? == 0 || line.trim() == ":quit" {
+                return Ok(None);
+            }
+            reply = self.command(&line)?;
+        }
+    }
+}
+
+/// Hands the droid at `path` over to the player, picking up from the
+/// transcript at `transcript` if there is one and saving to it as they go.
+/// Returns the airlock password, or `None` if the player quit first.
+pub fn play(path: &str, transcript: &str) -> io::Result<Option<String>> {
+    let mut session = Session::from_file(path);
+    if let Ok(saved) = std::fs::read_to_string(transcript) {
+        session.replay(&saved)?;
+    }
+    let mut session = session.with_transcript(transcript);
+    println!("Type :help for session commands.");
+    let ending = session.interact(io::stdin().lock(), io::stdout())?;
+    Ok(ending.map(|text| password(&text).unwrap_or(text)))
+}
@@ -0,0 +1,170 @@
+use std::fmt;
+
+use super::program::{Observer, Program, Status};
+
+#[derive(Debug, PartialEq)]
+pub enum AsciiError {
+    /// A line to send held something other than ASCII.
+    InvalidInput(String),
+    /// The program printed `value`, which is not ASCII, along with `text`.
+    NonAscii { value: i64, text: String },
+    /// The program stopped without printing `prompt`.
+    NoPrompt { prompt: String, text: String },
+    /// The program stopped without printing a non-ASCII value.
+    NoResult(String),
+    /// The step budget ran out before the program stopped for input.
+    BudgetExhausted(String),
+}
+
+impl fmt::Display for AsciiError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            AsciiError::InvalidInput(line) => write!(f, "cannot send non-ASCII line {:?}", line),
+            AsciiError::NonAscii { value, text } => {
+                write!(f, "non-ASCII output {} among:\n{}", value, text)
+            }
+            AsciiError::NoPrompt { prompt, text } => {
+                write!(f, "expected prompt {:?}, got:\n{}", prompt, text)
+            }
+            AsciiError::NoResult(text) => write!(f, "no non-ASCII result after:\n{}", text),
+            AsciiError::BudgetExhausted(text) => {
+                write!(f, "step budget exhausted after:\n{}", text)
+            }
+        }
+    }
+}
+
+/// Talks to a program that reads and prints ASCII text. Lines go in as
+/// character codes with a trailing newline; output comes back as text, with
+/// carriage returns dropped. Text the program has printed but no caller has
+/// asked for yet is kept until the next read.
+#[derive(Clone)]
+pub struct AsciiProgram {
+    program: Program,
+    buffer: String,
+}
+
+impl AsciiProgram {
+    pub fn new(program: Program) -> Self {
+        Self {
+            program,
+            buffer: String::new(),
+        }
+    }
+
+    pub fn program(&self) -> &Program {
+        &self.program
+    }
+
+    pub fn program_mut(&mut self) -> &mut Program {
+        &mut self.program
+    }
+
+    pub fn status(&self) -> &Status {
+        self.program.status()
+    }
+
+    pub fn is_halted(&self) -> bool {
+        self.program.status() == &Status::Halted
+    }
+
+    /// Queues `line` and a newline as input. Nothing runs until the next
+    /// read.
+    pub fn send_line(&mut self, line: &str) -> Result<(), AsciiError> {
+        if !line.is_ascii() {
+            return Err(AsciiError::InvalidInput(line.to_string()));
+        }
+        self.program
+            .get_pending_inputs()
+            .extend(line.bytes().chain([b'\n']).map(|b| b as i64));
+        Ok(())
+    }
+
+    /// Runs until the program wants input or halts and returns everything
+    /// it printed. If some of it was not ASCII, the error holds the first
+    /// such value and all the text printed around it.
+    pub fn read_text(&mut self) -> Result<String, AsciiError> {
+        self.run()?;
+        let outputs = self.program.read_all_outputs();
+        let mut rest = &outputs[..];
+        let mut first = None;
+        while let Some((value, at)) = self.decode(rest.iter().copied()) {
+            first.get_or_insert(value);
+            rest = &rest[at + 1..];
+        }
+        match first {
+            Some(value) => Err(AsciiError::NonAscii {
+                value,
+                text: std::mem::take(&mut self.buffer),
+            }),
+            None => Ok(std::mem::take(&mut self.buffer)),
+        }
+    }
+
+    /// Returns printed text up to and including the first `prompt`, keeping
+    /// whatever follows it for the next read.
+    pub fn read_until_prompt(&mut self, prompt: &str) -> Result<String, AsciiError> {
+        if !self.buffer.contains(prompt) {
+            self.buffer = self.read_text()?;
+        }
+        match self.buffer.find(prompt) {
+            Some(start) => {
+                let rest = self.buffer.split_off(start + prompt.len());
+                Ok(std::mem::replace(&mut self.buffer, rest))
+            }
+            None => Err(AsciiError::NoPrompt {
+                prompt: prompt.to_string(),
+                text: std::mem::take(&mut self.buffer),
+            }),
+        }
+    }
+
+    /// Runs until the program wants input or halts and returns the single
+    /// non-ASCII value it printed, which is how programs report a numeric
+    /// answer. Text printed around it is kept for the next read.
+    pub fn take_nonascii_result(&mut self) -> Result<i64, AsciiError> {
+        self.run()?;
+        let outputs = self.program.read_all_outputs();
+        let Some((value, at)) = self.decode(outputs.iter().copied()) else {
+            return Err(AsciiError::NoResult(std::mem::take(&mut self.buffer)));
+        };
+        if let Some((value, _)) = self.decode(outputs[at + 1..].iter().copied()) {
+            return Err(AsciiError::NonAscii {
+                value,
+                text: std::mem::take(&mut self.buffer),
+            });
+        }
+        Ok(value)
+    }
+
+    fn run(&mut self) -> Result<(), AsciiError> {
+        self.run_observed(&mut ())
+    }
+
+    /// Runs until the program wants input or halts, showing every executed
+    /// instruction to `observer`. What it printed is kept for the next read.
+    pub fn run_observed(&mut self, observer: &mut impl Observer) -> Result<(), AsciiError> {
+        self.program.run_observed(observer);
+        if self.program.status() == &Status::BudgetExhausted {
+            let outputs = self.program.read_all_outputs();
+            self.decode(outputs);
+            return Err(AsciiError::BudgetExhausted(std::mem::take(
+                &mut self.buffer,
+            )));
+        }
+        Ok(())
+    }
+
+    /// Appends outputs to the buffer up to the first non-ASCII value, which
+    /// is returned along with its position.
+    fn decode(&mut self, outputs: impl IntoIterator<Item = i64>) -> Option<(i64, usize)> {
+        for (i, value) in outputs.into_iter().enumerate() {
+            match value {
+                13 => {}
+                0..=127 => self.buffer.push(value as u8 as char),
+                _ => return Some((value, i)),
+            }
+        }
+        None
+    }
+}
@@ -0,0 +1,79 @@
+use std::time::{Duration, Instant};
+
+use super::fast::FastProgram;
+use super::{read_intcode, Program};
+
+/// How the reference VM and the fast one did on the same run.
+#[derive(Debug)]
+pub struct Comparison {
+    pub name: String,
+    pub steps: u64,
+    pub reference: Duration,
+    pub fast: Duration,
+    /// Whether both produced the same outputs and final memory.
+    pub agree: bool,
+}
+
+impl Comparison {
+    pub fn speedup(&self) -> f64 {
+        self.reference.as_secs_f64() / self.fast.as_secs_f64().max(f64::MIN_POSITIVE)
+    }
+}
+
+/// Runs `intcode` with `inputs` on both VMs `repeats` times each, until it
+/// halts or wants more input, and keeps the best time of each.
+pub fn compare(name: &str, intcode: &[i64], inputs: &[i64], repeats: u32) -> Comparison {
+    let mut reference = Duration::MAX;
+    let mut fast = Duration::MAX;
+    let mut agree = true;
+    let mut steps = 0;
+    for _ in 0..repeats.max(1) {
+        let start = Instant::now();
+        let mut slow_vm = Program::new(intcode.to_vec(), inputs.to_vec());
+        slow_vm.run();
+        reference = reference.min(start.elapsed());
+
+        let start = Instant::now();
+        let mut fast_vm = FastProgram::new(intcode.to_vec(), inputs.to_vec());
+        fast_vm.run();
+        fast = fast.min(start.elapsed());
+
+        steps = fast_vm.steps();
+        agree &= slow_vm.steps() == steps
+            && slow_vm.status() == fast_vm.status()
+            && slow_vm.read_all_outputs() == fast_vm.read_all_outputs()
+            && slow_vm.memory() == fast_vm.memory();
+    }
+    Comparison {
+        name: name.to_string(),
+        steps,
+        reference,
+        fast,
+        agree,
+    }
+}
+
+/// Times day09's BOOST in sensor boost mode, and day25's droid taking a
+/// hundred inventories, which works on any ship.
+pub fn run() {
+    let inventory = "inv\n"
+        .repeat(100)
+        .bytes()
+        .map(|b| b as i64)
+        .collect::<Vec<_>>();
+    let runs = [
+        compare("day09 BOOST", &read_intcode("inputs/day09"), &[2], 5),
+        compare("day25 droid", &read_intcode("inputs/day25"), &inventory, 5),
+    ];
+    for c in runs {
+        println!(
+            "{:<12} {:>10} steps  reference {:>9.2?}  fast {:>9.2?}  {:>5.1}x{}",
+            c.name,
+            c.steps,
+            c.reference,
+            c.fast,
+            c.speedup(),
+            if c.agree { "" } else { "  OUTPUTS DIFFER" }
+        );
+    }
+}
@@ -0,0 +1,308 @@
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt;
+
+use super::opcode::{Opcode, Operation, ParameterType};
+
+/// An instruction as it sits in memory, before anything has run.
+#[derive(Clone, Copy, Debug)]
+pub struct Instruction {
+    pub address: usize,
+    /// `None` for a halt.
+    pub opcode: Option<Opcode>,
+    pub operands: [i64; 3],
+}
+
+impl Instruction {
+    /// Decodes the instruction at `address`, or `None` if the cell does not
+    /// hold one.
+    pub fn decode(memory: &[i64], address: usize) -> Option<Self> {
+        let value = *memory.get(address)?;
+        if value == 99 {
+            return Some(Self {
+                address,
+                opcode: None,
+                operands: [0; 3],
+            });
+        }
+        let opcode = Opcode::try_from_int(value)?;
+        let mut operands = [0; 3];
+        for (i, operand) in operands
+            .iter_mut()
+            .enumerate()
+            .take(opcode.variables as usize)
+        {
+            *operand = memory.get(address + i + 1).copied().unwrap_or(0);
+        }
+        Some(Self {
+            address,
+            opcode: Some(opcode),
+            operands,
+        })
+    }
+
+    /// Cells the instruction takes up.
+    pub fn size(&self) -> usize {
+        self.opcode.map_or(1, |o| o.variables as usize + 1)
+    }
+
+    /// The value of operand `i` if it is immediate.
+    fn immediate(&self, i: usize) -> Option<i64> {
+        let opcode = self.opcode?;
+        (opcode.parameter_modes[i] == ParameterType::Immediate).then_some(self.operands[i])
+    }
+
+    fn operand(&self, i: usize) -> String {
+        let value = self.operands[i];
+        match self.opcode.map(|o| o.parameter_modes[i]) {
+            Some(ParameterType::Position) => format!("[{}]", value),
+            Some(ParameterType::Relative) if value < 0 => format!("[rb{}]", value),
+            Some(ParameterType::Relative) => format!("[rb+{}]", value),
+            _ => value.to_string(),
+        }
+    }
+}
+
+impl fmt::Display for Instruction {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let Some(opcode) = self.opcode else {
+            return write!(f, "{:>5}: Halt", self.address);
+        };
+        let operands = (0..opcode.variables as usize)
+            .map(|i| self.operand(i))
+            .collect::<Vec<_>>();
+        write!(
+            f,
+            "{:>5}: {:?} {}",
+            self.address,
+            opcode.opcode,
+            operands.join(", ")
+        )
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum Target {
+    Address(usize),
+    /// Read from memory or the relative base at run time, shown as the
+    /// operand it comes from.
+    Computed(String),
+}
+
+impl fmt::Display for Target {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Target::Address(address) => write!(f, "{}", address),
+            Target::Computed(operand) => write!(f, "{}", operand),
+        }
+    }
+}
+
+/// How control leaves a basic block.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Exit {
+    Halt,
+    /// Runs straight on into the block at this address.
+    Fallthrough(usize),
+    Branch {
+        taken: Target,
+        fallthrough: usize,
+    },
+    Jump(Target),
+    /// A jump after storing the address just past it, which the code
+    /// jumped to is expected to come back to.
+    Call {
+        target: usize,
+        returns_to: usize,
+    },
+    /// The next cell is not an instruction.
+    Invalid(usize),
+}
+
+#[derive(Clone, Debug)]
+pub struct Block {
+    pub start: usize,
+    pub instructions: Vec<Instruction>,
+    pub exit: Exit,
+}
+
+impl Block {
+    pub fn successors(&self) -> Vec<usize> {
+        match &self.exit {
+            Exit::Fallthrough(next) => vec![*next],
+            Exit::Branch { taken, fallthrough } => match taken {
+                Target::Address(target) => vec![*target, *fallthrough],
+                Target::Computed(_) => vec![*fallthrough],
+            },
+            Exit::Jump(Target::Address(target)) => vec![*target],
+            Exit::Call { target, returns_to } => vec![*target, *returns_to],
+            Exit::Jump(Target::Computed(_)) | Exit::Halt | Exit::Invalid(_) => vec![],
+        }
+    }
+
+    pub fn has_computed_jump(&self) -> bool {
+        matches!(
+            &self.exit,
+            Exit::Jump(Target::Computed(_))
+                | Exit::Branch {
+                    taken: Target::Computed(_),
+                    ..
+                }
+        )
+    }
+}
+
+/// Basic blocks reachable from address 0 in a program's initial memory.
+/// Code that only runs after the program rewrites itself, or that is only
+/// reached through computed jumps, is not found.
+#[derive(Debug)]
+pub struct Cfg {
+    pub blocks: BTreeMap<usize, Block>,
+}
+
+impl Cfg {
+    pub fn recover(memory: &[i64]) -> Self {
+        let mut leaders = BTreeSet::from([0]);
+        loop {
+            let blocks = leaders
+                .iter()
+                .map(|&start| (start, walk(memory, start, &leaders)))
+                .collect::<BTreeMap<_, _>>();
+            let before = leaders.len();
+            leaders.extend(blocks.values().flat_map(|b| b.successors()));
+            if leaders.len() == before {
+                return Self { blocks };
+            }
+        }
+    }
+
+    /// Addresses of jumps whose target is only known at run time.
+    pub fn computed_jumps(&self) -> Vec<usize> {
+        self.blocks
+            .values()
+            .filter(|b| b.has_computed_jump())
+            .filter_map(|b| b.instructions.last().map(|i| i.address))
+            .collect()
+    }
+
+    pub fn to_dot(&self) -> String {
+        let mut dot = String::from("digraph intcode {\n  node [shape=box fontname=monospace];\n");
+        for block in self.blocks.values() {
+            let mut label = block
+                .instructions
+                .iter()
+                .map(|i| format!("{}\\l", escape(&i.to_string())))
+                .collect::<String>();
+            match &block.exit {
+                Exit::Invalid(address) => label += &format!("{:>5}: ???\\l", address),
+                Exit::Jump(target @ Target::Computed(_))
+                | Exit::Branch {
+                    taken: target @ Target::Computed(_),
+                    ..
+                } => label += &format!("jumps to {}\\l", escape(&target.to_string())),
+                _ => {}
+            }
+            let style = if block.has_computed_jump() {
+                " color=red"
+            } else {
+                ""
+            };
+            dot += &format!("  b{} [label=\"{}\"{}];\n", block.start, label, style);
+
+            let edges = match &block.exit {
+                Exit::Fallthrough(next) => vec![(*next, "")],
+                Exit::Branch { taken, fallthrough } => {
+                    let mut edges = vec![(*fallthrough, "label=\"not taken\"")];
+                    if let Target::Address(target) = taken {
+                        edges.push((*target, "label=\"taken\""));
+                    }
+                    edges
+                }
+                Exit::Jump(Target::Address(target)) => vec![(*target, "")],
+                Exit::Call { target, returns_to } => vec![
+                    (*target, "label=\"call\""),
+                    (*returns_to, "label=\"return\" style=dashed"),
+                ],
+                _ => vec![],
+            };
+            for (to, attributes) in edges {
+                dot += &format!("  b{} -> b{} [{}];\n", block.start, to, attributes);
+            }
+        }
+        dot + "}\n"
+    }
+}
+
+/// Decodes from `start` until control leaves the block or runs into
+/// another leader.
+fn walk(memory: &[i64], start: usize, leaders: &BTreeSet<usize>) -> Block {
+    let mut instructions: Vec<Instruction> = vec![];
+    let mut address = start;
+    let exit = loop {
+        let Some(instruction) = Instruction::decode(memory, address) else {
+            break Exit::Invalid(address);
+        };
+        instructions.push(instruction);
+        let next = address + instruction.size();
+        let Some(opcode) = instruction.opcode else {
+            break Exit::Halt;
+        };
+        let jumps_if = match opcode.opcode {
+            Operation::JumpIfTrue => Some(true),
+            Operation::JumpIfFalse => Some(false),
+            _ => None,
+        };
+        if let Some(jumps_if) = jumps_if {
+            let target = match instruction.immediate(1) {
+                Some(target) if target >= 0 => Target::Address(target as usize),
+                _ => Target::Computed(instruction.operand(1)),
+            };
+            match instruction.immediate(0).map(|c| (c != 0) == jumps_if) {
+                Some(false) => {}
+                Some(true) => break unconditional(&instructions, target, next),
+                None => {
+                    break Exit::Branch {
+                        taken: target,
+                        fallthrough: next,
+                    }
+                }
+            }
+        }
+        if leaders.contains(&next) {
+            break Exit::Fallthrough(next);
+        }
+        address = next;
+    };
+    Block {
+        start,
+        instructions,
+        exit,
+    }
+}
+
+/// An unconditional jump is taken as a call when the same block stores
+/// the address right after it, as a return address, from immediates.
+fn unconditional(instructions: &[Instruction], target: Target, next: usize) -> Exit {
+    let Target::Address(target) = target else {
+        return Exit::Jump(target);
+    };
+    let stores_return = instructions.iter().any(|i| {
+        let value = match (i.opcode.map(|o| o.opcode), i.immediate(0), i.immediate(1)) {
+            (Some(Operation::Add), Some(a), Some(b)) => a + b,
+            (Some(Operation::Multiply), Some(a), Some(b)) => a * b,
+            _ => return false,
+        };
+        value == next as i64
+    });
+    if stores_return {
+        Exit::Call {
+            target,
+            returns_to: next,
+        }
+    } else {
+        Exit::Jump(Target::Address(target))
+    }
+}
+
+fn escape(text: &str) -> String {
+    text.replace('\\', "\\\\").replace('"', "\\\"")
+}
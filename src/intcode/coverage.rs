@@ -0,0 +1,310 @@
+use std::collections::{BTreeSet, HashMap};
+use std::fmt;
+
+use super::opcode::ParameterType;
+use super::program::{Event, Observer};
+use super::{read_intcode, Program, Status};
+
+const HEAT: &[u8] = b" .:-=+*#%@";
+const HEATMAP_WIDTH: usize = 64;
+const TOP: usize = 10;
+
+/// An instruction that ran from memory the program had itself written.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct SelfModification {
+    /// Address of the instruction.
+    pub ipointer: usize,
+    /// The cell within the instruction that had been overwritten.
+    pub address: usize,
+    pub written_at: u64,
+    pub executed_at: u64,
+}
+
+/// A backward jump and how often it was taken.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct HotLoop {
+    pub start: usize,
+    pub end: usize,
+    pub iterations: u64,
+}
+
+/// Watches a run and records how each address was used: as instruction
+/// code, as data read by an operand, or as the target of a write.
+#[derive(Default)]
+pub struct Coverage {
+    executed: HashMap<usize, u64>,
+    code: BTreeSet<usize>,
+    read: BTreeSet<usize>,
+    writes: HashMap<usize, u64>,
+    last_write: HashMap<usize, u64>,
+    back_jumps: HashMap<(usize, usize), u64>,
+    self_modifications: Vec<SelfModification>,
+}
+
+impl Coverage {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Summary over a memory of `size` cells, keeping the `top` hottest
+    /// loops and written cells.
+    pub fn report(&self, size: usize, top: usize) -> CoverageReport {
+        let mut hot_loops = self
+            .back_jumps
+            .iter()
+            .map(|(&(end, start), &iterations)| HotLoop {
+                start,
+                end,
+                iterations,
+            })
+            .collect::<Vec<_>>();
+        hot_loops.sort_by_key(|l| (std::cmp::Reverse(l.iterations), l.start));
+        hot_loops.truncate(top);
+
+        let mut hot_writes = self
+            .writes
+            .iter()
+            .map(|(&a, &n)| (a, n))
+            .collect::<Vec<_>>();
+        hot_writes.sort_by_key(|&(a, n)| (std::cmp::Reverse(n), a));
+        hot_writes.truncate(top);
+
+        CoverageReport {
+            size,
+            code: self.code.len(),
+            read: self.read.len(),
+            written: self.writes.len(),
+            instructions: self.executed.len(),
+            hot_loops,
+            hot_writes,
+            self_modifications: self.self_modifications.clone(),
+        }
+    }
+
+    /// Addresses used as instruction code, including operand cells.
+    pub fn code(&self) -> &BTreeSet<usize> {
+        &self.code
+    }
+
+    /// Addresses read as data through position or relative operands.
+    pub fn read(&self) -> &BTreeSet<usize> {
+        &self.read
+    }
+
+    /// How many times each address was written.
+    pub fn writes(&self) -> &HashMap<usize, u64> {
+        &self.writes
+    }
+
+    /// One past the highest address the run used in any way.
+    pub fn extent(&self) -> usize {
+        let code = self.code.last().map_or(0, |&a| a + 1);
+        let read = self.read.last().map_or(0, |&a| a + 1);
+        let written = self.writes.keys().max().map_or(0, |&a| a + 1);
+        code.max(read).max(written)
+    }
+
+    /// The report and heatmap over a memory of `size` cells, as printed by
+    /// `--coverage`.
+    pub fn summary(&self, size: usize) -> String {
+        format!(
+            "{}{}",
+            self.report(size, TOP),
+            self.heatmap(size, HEATMAP_WIDTH)
+        )
+    }
+
+    /// Write counts drawn `width` cells to a row, darker for more writes on
+    /// a log scale. Each row starts with the address of its first cell.
+    pub fn heatmap(&self, size: usize, width: usize) -> String {
+        let max = self.writes.values().copied().max().unwrap_or(0);
+        let scale = ((max + 1) as f64).ln().max(f64::MIN_POSITIVE);
+        let mut map = String::new();
+        for start in (0..size).step_by(width.max(1)) {
+            map += &format!("{:>6} ", start);
+            for address in start..(start + width).min(size) {
+                let n = self.writes.get(&address).copied().unwrap_or(0);
+                let level = ((n as f64 + 1.0).ln() / scale * (HEAT.len() - 1) as f64).round();
+                map.push(HEAT[level as usize] as char);
+            }
+            map.push('\n');
+        }
+        map
+    }
+}
+
+impl Observer for Coverage {
+    fn observe(&mut self, event: &Event) {
+        let ip = event.ipointer;
+        *self.executed.entry(ip).or_insert(0) += 1;
+        for address in ip..=ip + event.opcode.variables as usize {
+            self.code.insert(address);
+            // Each write is reported the first time it is executed only.
+            if let Some(written_at) = self.last_write.remove(&address) {
+                self.self_modifications.push(SelfModification {
+                    ipointer: ip,
+                    address,
+                    written_at,
+                    executed_at: event.step,
+                });
+            }
+        }
+
+        let destination = event.opcode.opcode.destination();
+        for (i, (param, mode)) in event
+            .params
+            .iter()
+            .zip(event.opcode.parameter_modes)
+            .enumerate()
+        {
+            if Some(i) != destination && mode != ParameterType::Immediate {
+                self.read.insert(param.index);
+            }
+        }
+
+        if let Some(write) = event.write {
+            *self.writes.entry(write.index).or_insert(0) += 1;
+            self.last_write.insert(write.index, event.step);
+        }
+        if let Some(target) = event.jump.filter(|&t| t <= ip) {
+            *self.back_jumps.entry((ip, target)).or_insert(0) += 1;
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct CoverageReport {
+    pub size: usize,
+    pub code: usize,
+    pub read: usize,
+    pub written: usize,
+    /// Distinct instruction addresses executed.
+    pub instructions: usize,
+    pub hot_loops: Vec<HotLoop>,
+    pub hot_writes: Vec<(usize, u64)>,
+    pub self_modifications: Vec<SelfModification>,
+}
+
+impl CoverageReport {
+    fn percent(&self, n: usize) -> f64 {
+        if self.size == 0 {
+            0.0
+        } else {
+            n as f64 * 100.0 / self.size as f64
+        }
+    }
+}
+
+impl fmt::Display for CoverageReport {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "Memory: {} cells", self.size)?;
+        writeln!(
+            f,
+            "Code:    {:>6} cells ({:.1}%), {} instructions",
+            self.code,
+            self.percent(self.code),
+            self.instructions
+        )?;
+        writeln!(
+            f,
+            "Read:    {:>6} cells ({:.1}%)",
+            self.read,
+            self.percent(self.read)
+        )?;
+        writeln!(
+            f,
+            "Written: {:>6} cells ({:.1}%)",
+            self.written,
+            self.percent(self.written)
+        )?;
+        writeln!(f, "Hot loops:")?;
+        for l in &self.hot_loops {
+            writeln!(f, "  {:>6}..={:<6} {} times", l.start, l.end, l.iterations)?;
+        }
+        writeln!(f, "Most written:")?;
+        for (address, n) in &self.hot_writes {
+            writeln!(f, "  {:>6} {} times", address, n)?;
+        }
+        if self.self_modifications.is_empty() {
+            writeln!(f, "No self-modifying code")?;
+        } else {
+            writeln!(f, "Self-modifying code:")?;
+            for m in &self.self_modifications {
+                writeln!(
+                    f,
+                    "  instruction at {} ran cell {} (written at step {}) at step {}",
+                    m.ipointer, m.address, m.written_at, m.executed_at
+                )?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Runs the program at `path` on `inputs` until it halts or wants more
+/// input, then prints its coverage report and a write heatmap of the memory
+/// it ended with.
+pub fn run(path: &str, inputs: Vec<i64>) {
+    let mut coverage = Coverage::new();
+    let mut program = Program::new(read_intcode(path), inputs);
+    program.run_observed(&mut coverage);
+    if *program.status() == Status::WaitingForInput {
+        println!("Stopped waiting for input after {} steps", program.steps());
+    }
+    print!("{}", coverage.summary(program.memory().len()));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn counts_a_self_modifying_program() {
+        // Stores 4 in cell 21, then writes 4 + 95 over the operand of the
+        // output at 8, which prints 99 instead of 0.
+        let intcode = vec![1101, 2, 2, 21, 1001, 21, 95, 9, 104, 0, 99];
+        let mut program = Program::new(intcode, vec![]);
+        let mut coverage = Coverage::new();
+        program.run_observed(&mut coverage);
+        assert_eq!(program.read_all_outputs(), vec![99]);
+
+        let report = coverage.report(program.memory().len(), 10);
+        assert_eq!(report.size, 22);
+        assert_eq!(report.code, 10);
+        assert_eq!(report.read, 1);
+        assert_eq!(report.written, 2);
+        assert_eq!(report.instructions, 3);
+        assert_eq!(report.hot_writes, vec![(9, 1), (21, 1)]);
+        assert_eq!(
+            report.self_modifications,
+            vec![SelfModification {
+                ipointer: 8,
+                address: 9,
+                written_at: 1,
+                executed_at: 2,
+            }]
+        );
+        assert_eq!(coverage.extent(), 22);
+    }
+
+    #[test]
+    fn counts_back_jumps() {
+        // Counts cell 20 down from 3 and jumps back while it is non-zero.
+        let intcode = vec![1101, 0, 3, 20, 1001, 20, -1, 20, 1005, 20, 4, 99];
+        let mut program = Program::new(intcode, vec![]);
+        let mut coverage = Coverage::new();
+        program.run_observed(&mut coverage);
+
+        let report = coverage.report(program.memory().len(), 10);
+        assert_eq!(
+            report.hot_loops,
+            vec![HotLoop {
+                start: 4,
+                end: 8,
+                iterations: 2,
+            }]
+        );
+        assert_eq!(report.hot_writes, vec![(20, 4)]);
+        assert!(report.self_modifications.is_empty());
+    }
+}
@@ -0,0 +1,184 @@
+use super::opcode::{Opcode, Operation, ParameterType};
+use super::program::Status;
+
+/// An instruction with its modes split out and its operands copied, so
+/// running it again needs no decoding.
+#[derive(Clone, Copy, Debug)]
+struct Decoded {
+    operation: Operation,
+    modes: [ParameterType; 3],
+    operands: [i64; 3],
+    len: usize,
+}
+
+/// A VM for speed rather than inspection: it runs the same programs as
+/// [`Program`](super::Program) and decodes each instruction once, keeping
+/// the result until something writes over one of its cells. No heap
+/// allocation happens per instruction unless memory has to grow.
+#[derive(Clone)]
+pub struct FastProgram {
+    memory: Vec<i64>,
+    /// Decoded instruction starting at each address, if it has run.
+    cache: Vec<Option<Decoded>>,
+    status: Status,
+    ipointer: usize,
+    relative_base: i64,
+    pending_inputs: Vec<i64>,
+    pending_outputs: Vec<i64>,
+    steps: u64,
+    budget: Option<u64>,
+}
+
+impl FastProgram {
+    pub fn new(intcode: Vec<i64>, pending_inputs: Vec<i64>) -> Self {
+        Self {
+            cache: vec![None; intcode.len()],
+            memory: intcode,
+            status: Status::Idle,
+            ipointer: 0,
+            relative_base: 0,
+            pending_inputs,
+            pending_outputs: vec![],
+            steps: 0,
+            budget: None,
+        }
+    }
+
+    /// Stops `run` with `Status::BudgetExhausted` after `steps` more
+    /// instructions.
+    pub fn with_budget(mut self, steps: u64) -> Self {
+        self.budget = Some(steps);
+        self
+    }
+
+    pub fn steps(&self) -> u64 {
+        self.steps
+    }
+
+    pub fn get_pending_inputs(&mut self) -> &mut Vec<i64> {
+        &mut self.pending_inputs
+    }
+
+    pub fn read_all_outputs(&mut self) -> Vec<i64> {
+        std::mem::take(&mut self.pending_outputs)
+    }
+
+    pub fn read_outputs(&mut self, max: usize) -> Vec<i64> {
+        let max = std::cmp::min(max, self.pending_outputs.len());
+        self.pending_outputs.drain(..max).collect()
+    }
+
+    pub fn status(&self) -> &Status {
+        &self.status
+    }
+
+    pub fn memory(&self) -> &[i64] {
+        &self.memory
+    }
+
+    fn decode(&mut self, address: usize) -> Decoded {
+        if let Some(decoded) = self.cache[address] {
+            return decoded;
+        }
+        let opcode = Opcode::from_int(self.memory[address] as u32);
+        let len = opcode.variables as usize + 1;
+        let mut operands = [0; 3];
+        for (i, operand) in operands.iter_mut().enumerate().take(len - 1) {
+            *operand = self.load(address + i + 1);
+        }
+        let decoded = Decoded {
+            operation: opcode.opcode,
+            modes: opcode.parameter_modes,
+            operands,
+            len,
+        };
+        self.cache[address] = Some(decoded);
+        decoded
+    }
+
+    fn load(&self, address: usize) -> i64 {
+        self.memory.get(address).copied().unwrap_or(0)
+    }
+
+    fn address(&self, mode: ParameterType, operand: i64) -> usize {
+        let address = match mode {
+            ParameterType::Position => operand,
+            ParameterType::Relative => operand + self.relative_base,
+            ParameterType::Immediate => panic!("cannot take the address of an immediate"),
+        };
+        if address < 0 {
+            panic!("attempted to access negative index");
+        }
+        address as usize
+    }
+
+    fn value(&self, decoded: &Decoded, i: usize) -> i64 {
+        match decoded.modes[i] {
+            ParameterType::Immediate => decoded.operands[i],
+            mode => self.load(self.address(mode, decoded.operands[i])),
+        }
+    }
+
+    /// Writes `value` and forgets any decoded instruction that could have
+    /// covered the cell, which is one starting up to three cells before it.
+    fn store(&mut self, decoded: &Decoded, i: usize, value: i64) {
+        let address = self.address(decoded.modes[i], decoded.operands[i]);
+        if address >= self.memory.len() {
+            self.memory.resize(address + 1, 0);
+            self.cache.resize(address + 1, None);
+        }
+        self.memory[address] = value;
+        for entry in &mut self.cache[address.saturating_sub(3)..=address] {
+            *entry = None;
+        }
+    }
+
+    pub fn run(&mut self) {
+        self.status = Status::Running;
+        while self.memory[self.ipointer] != 99 {
+            if self.budget == Some(0) {
+                self.status = Status::BudgetExhausted;
+                return;
+            }
+            let d = self.decode(self.ipointer);
+            if d.operation == Operation::Input && self.pending_inputs.is_empty() {
+                self.status = Status::WaitingForInput;
+                return;
+            }
+            self.steps += 1;
+            if let Some(budget) = self.budget.as_mut() {
+                *budget -= 1;
+            }
+
+            let mut next = self.ipointer + d.len;
+            match d.operation {
+                Operation::Add => self.store(&d, 2, self.value(&d, 0) + self.value(&d, 1)),
+                Operation::Multiply => self.store(&d, 2, self.value(&d, 0) * self.value(&d, 1)),
+                Operation::Input => {
+                    let input = self.pending_inputs.remove(0);
+                    self.store(&d, 0, input);
+                }
+                Operation::Output => self.pending_outputs.push(self.value(&d, 0)),
+                Operation::JumpIfTrue => {
+                    if self.value(&d, 0) != 0 {
+                        next = self.value(&d, 1) as usize;
+                    }
+                }
+                Operation::JumpIfFalse => {
+                    if self.value(&d, 0) == 0 {
+                        next = self.value(&d, 1) as usize;
+                    }
+                }
+                Operation::LessThan => {
+                    self.store(&d, 2, (self.value(&d, 0) < self.value(&d, 1)) as i64)
+                }
+                Operation::Equals => {
+                    self.store(&d, 2, (self.value(&d, 0) == self.value(&d, 1)) as i64)
+                }
+                Operation::AdjustRelativeBase => self.relative_base += self.value(&d, 0),
+            }
+            self.ipointer = next;
+        }
+        self.status = Status::Halted;
+    }
+}
@@ -0,0 +1,21 @@
+#![allow(dead_code)]
+pub mod ascii;
+pub mod bench;
+pub mod cfg;
+pub mod coverage;
+pub mod fast;
+pub mod opcode;
+pub mod patch;
+pub mod program;
+pub mod trace;
+
+pub use program::{Program, Status};
+
+pub fn read_intcode(path: &str) -> Vec<i64> {
+    std::fs::read_to_string(path)
+        .expect("File should be there")
+        .trim()
+        .split(',')
+        .map(|n| n.parse::<i64>().expect("Failed to parse int"))
+        .collect()
+}
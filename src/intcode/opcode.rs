@@ -0,0 +1,144 @@
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Parameter {
+    pub index: usize,
+    pub value: i64,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ParameterType {
+    Position,
+    Immediate,
+    Relative,
+}
+
+impl ParameterType {
+    pub fn from_int(instruction: u32) -> [ParameterType; 3] {
+        let mut val = instruction;
+        let mut types: [ParameterType; 3] = [ParameterType::Position; 3];
+        for mode in types.iter_mut() {
+            *mode = match val % 10 {
+                0 => ParameterType::Position,
+                1 => ParameterType::Immediate,
+                2 => ParameterType::Relative,
+                _ => panic!("invalid parameter mode"),
+            };
+            val /= 10;
+        }
+        types
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Operation {
+    Add,
+    Multiply,
+    Input,
+    Output,
+    JumpIfTrue,
+    JumpIfFalse,
+    LessThan,
+    Equals,
+    AdjustRelativeBase,
+}
+
+impl Operation {
+    pub const ALL: [Operation; 9] = [
+        Operation::Add,
+        Operation::Multiply,
+        Operation::Input,
+        Operation::Output,
+        Operation::JumpIfTrue,
+        Operation::JumpIfFalse,
+        Operation::LessThan,
+        Operation::Equals,
+        Operation::AdjustRelativeBase,
+    ];
+
+    pub fn from_int(opcode: u32) -> Self {
+        match opcode {
+            1 => Operation::Add,
+            2 => Operation::Multiply,
+            3 => Operation::Input,
+            4 => Operation::Output,
+            5 => Operation::JumpIfTrue,
+            6 => Operation::JumpIfFalse,
+            7 => Operation::LessThan,
+            8 => Operation::Equals,
+            9 => Operation::AdjustRelativeBase,
+            _ => panic!("invalid opcode"),
+        }
+    }
+
+    /// The two-digit opcode, which is also this operation's position in
+    /// `ALL` plus one.
+    pub fn code(self) -> u32 {
+        self as u32 + 1
+    }
+
+    pub fn variables(self) -> u32 {
+        match self {
+            Operation::Add | Operation::Multiply | Operation::LessThan | Operation::Equals => 3,
+            Operation::JumpIfTrue | Operation::JumpIfFalse => 2,
+            Operation::Input | Operation::Output | Operation::AdjustRelativeBase => 1,
+        }
+    }
+
+    /// Which operand is the address written to, for operations that write.
+    pub fn destination(self) -> Option<usize> {
+        match self {
+            Operation::Input => Some(0),
+            Operation::Add | Operation::Multiply | Operation::LessThan | Operation::Equals => {
+                Some(2)
+            }
+            _ => None,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct Opcode {
+    pub parameter_modes: [ParameterType; 3],
+    pub opcode: Operation,
+    pub variables: u32,
+}
+
+impl Opcode {
+    pub fn from_int(instruction: u32) -> Self {
+        let opcode = Operation::from_int(instruction % 100);
+        Self {
+            parameter_modes: ParameterType::from_int(instruction / 100),
+            opcode,
+            variables: opcode.variables(),
+        }
+    }
+
+    /// Decodes `value` if it is a valid instruction, which 99 is not.
+    pub fn try_from_int(value: i64) -> Option<Self> {
+        let modes = value / 100;
+        let valid = (1..=9).contains(&(value % 100))
+            && (0..1000).contains(&modes)
+            && [modes % 10, modes / 10 % 10, modes / 100]
+                .iter()
+                .all(|&m| m <= 2);
+        valid.then(|| Self::from_int(value as u32))
+    }
+
+    pub fn get_params(&self, intcode: &[i64], ipointer: &usize, base: &i64) -> Vec<Parameter> {
+        let mut vars: Vec<Parameter> = vec![];
+        for i in 0..self.variables as usize {
+            let idx = match self.parameter_modes[i] {
+                ParameterType::Position => intcode[ipointer + i + 1],
+                ParameterType::Immediate => (ipointer + i + 1) as i64,
+                ParameterType::Relative => intcode[ipointer + i + 1] + base,
+            };
+            if idx < 0 {
+                panic!("attempted to access negative index");
+            }
+            vars.push(Parameter {
+                index: idx as usize,
+                value: intcode.get(idx as usize).copied().unwrap_or(0),
+            });
+        }
+        vars
+    }
+}
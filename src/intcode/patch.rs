@@ -0,0 +1,105 @@
+use std::fmt;
+use std::rc::Rc;
+
+/// Called with the value an operand is about to read; returns the value the
+/// instruction sees instead.
+pub type ReadHook = Rc<dyn Fn(i64) -> i64>;
+
+/// Called with a cell's old value and the value being written; returns the
+/// value actually stored.
+pub type WriteHook = Rc<dyn Fn(i64, i64) -> i64>;
+
+#[derive(Debug, PartialEq)]
+pub enum PatchError {
+    Parse { line: usize, text: String },
+}
+
+impl fmt::Display for PatchError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            PatchError::Parse { line, text } => write!(f, "line {}: cannot parse {:?}", line, text),
+        }
+    }
+}
+
+/// A named change to a program's memory before it runs: `values` are
+/// written starting at `address`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Patch {
+    pub name: String,
+    pub address: usize,
+    pub values: Vec<i64>,
+}
+
+impl Patch {
+    /// Sets the cell at `address` to `value`.
+    pub fn set(name: &str, address: usize, value: i64) -> Self {
+        Self::replace(name, address, &[value])
+    }
+
+    /// Overwrites the cells starting at `address` with `values`.
+    pub fn replace(name: &str, address: usize, values: &[i64]) -> Self {
+        Self {
+            name: name.to_string(),
+            address,
+            values: values.to_vec(),
+        }
+    }
+
+    /// Reads a patch written as `name: address = value,value,...`.
+    pub fn parse(text: &str) -> Option<Self> {
+        let (name, rest) = text.split_once(':')?;
+        let (address, values) = rest.split_once('=')?;
+        let values = values
+            .split(',')
+            .map(|v| v.trim().parse().ok())
+            .collect::<Option<Vec<_>>>()?;
+        Some(Self::replace(
+            name.trim(),
+            address.trim().parse().ok()?,
+            &values,
+        ))
+    }
+
+    /// Reads one patch per line, skipping blank lines and `#` comments.
+    pub fn parse_all(text: &str) -> Result<Vec<Self>, PatchError> {
+        let mut patches = vec![];
+        for (i, line) in text.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let patch = Self::parse(line).ok_or_else(|| PatchError::Parse {
+                line: i + 1,
+                text: line.to_string(),
+            })?;
+            patches.push(patch);
+        }
+        Ok(patches)
+    }
+
+    pub fn from_file(path: &str) -> Result<Vec<Self>, PatchError> {
+        Self::parse_all(&std::fs::read_to_string(path).expect("File should be there"))
+    }
+
+    /// Writes the patch into `memory`, growing it if the patch runs past
+    /// the end.
+    pub fn apply(&self, memory: &mut Vec<i64>) {
+        let end = self.address + self.values.len();
+        if memory.len() < end {
+            memory.resize(end, 0);
+        }
+        memory[self.address..end].copy_from_slice(&self.values);
+    }
+}
+
+impl fmt::Display for Patch {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let values = self
+            .values
+            .iter()
+            .map(|v| v.to_string())
+            .collect::<Vec<_>>();
+        write!(f, "{}: {} = {}", self.name, self.address, values.join(","))
+    }
+}
@@ -0,0 +1,266 @@
+use std::collections::HashMap;
+use std::rc::Rc;
+
+use super::opcode::{Opcode, Operation, Parameter, ParameterType};
+use super::patch::{Patch, ReadHook, WriteHook};
+
+/// A memory cell changed by an instruction.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Write {
+    pub index: usize,
+    pub old: i64,
+    pub new: i64,
+}
+
+/// One executed instruction, as seen by an [`Observer`].
+#[derive(Debug)]
+pub struct Event<'a> {
+    /// Instructions executed before this one.
+    pub step: u64,
+    pub ipointer: usize,
+    pub opcode: &'a Opcode,
+    /// Operands as resolved before the instruction ran.
+    pub params: &'a [Parameter],
+    pub write: Option<Write>,
+    /// Relative base before and after, if the instruction moved it.
+    pub relative_base: Option<(i64, i64)>,
+    /// Where a jump that was taken went.
+    pub jump: Option<usize>,
+}
+
+pub trait Observer {
+    fn observe(&mut self, event: &Event);
+}
+
+impl Observer for () {
+    fn observe(&mut self, _event: &Event) {}
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Status {
+    Idle,
+    Running,
+    WaitingForInput,
+    /// The step budget ran out. Raising the budget and calling `run` again
+    /// picks up where execution stopped.
+    BudgetExhausted,
+    Halted,
+}
+
+#[derive(Clone)]
+pub struct Program {
+    intcode: Vec<i64>,
+    status: Status,
+    ipointer: usize,
+    pending_inputs: Vec<i64>,
+    pending_outputs: Vec<i64>,
+    relative_base: i64,
+    counts: [u64; Operation::ALL.len()],
+    budget: Option<u64>,
+    read_hooks: HashMap<usize, ReadHook>,
+    write_hooks: HashMap<usize, WriteHook>,
+}
+
+impl Program {
+    pub fn new(intcode: Vec<i64>, pending_inputs: Vec<i64>) -> Self {
+        Self {
+            intcode,
+            ipointer: 0,
+            relative_base: 0,
+            status: Status::Idle,
+            pending_inputs,
+            pending_outputs: vec![],
+            counts: [0; Operation::ALL.len()],
+            budget: None,
+            read_hooks: HashMap::new(),
+            write_hooks: HashMap::new(),
+        }
+    }
+
+    pub fn with_patch(mut self, patch: &Patch) -> Self {
+        self.patch(patch);
+        self
+    }
+
+    pub fn patch(&mut self, patch: &Patch) {
+        patch.apply(&mut self.intcode);
+    }
+
+    /// Passes every operand read from `address` through `hook`. Reads of the
+    /// instruction's own cells, as immediate operands, are not intercepted.
+    /// Clones of the program share the hook.
+    pub fn on_read(&mut self, address: usize, hook: impl Fn(i64) -> i64 + 'static) {
+        self.read_hooks.insert(address, Rc::new(hook));
+    }
+
+    /// Passes every write to `address` through `hook`, which can keep the old
+    /// value to freeze the cell. Clones of the program share the hook.
+    pub fn on_write(&mut self, address: usize, hook: impl Fn(i64, i64) -> i64 + 'static) {
+        self.write_hooks.insert(address, Rc::new(hook));
+    }
+
+    pub fn clear_hooks(&mut self) {
+        self.read_hooks.clear();
+        self.write_hooks.clear();
+    }
+
+    /// Stops `run` with `Status::BudgetExhausted` after `steps` more
+    /// instructions.
+    pub fn with_budget(mut self, steps: u64) -> Self {
+        self.budget = Some(steps);
+        self
+    }
+
+    pub fn set_budget(&mut self, steps: Option<u64>) {
+        self.budget = steps;
+    }
+
+    /// Instructions left before the watchdog stops the program, if it is
+    /// being watched.
+    pub fn budget(&self) -> Option<u64> {
+        self.budget
+    }
+
+    /// Total instructions executed so far.
+    pub fn steps(&self) -> u64 {
+        self.counts.iter().sum()
+    }
+
+    /// How many times each operation has been executed.
+    pub fn instruction_counts(&self) -> Vec<(Operation, u64)> {
+        Operation::ALL.iter().copied().zip(self.counts).collect()
+    }
+
+    pub fn get_pending_inputs(&mut self) -> &mut Vec<i64> {
+        &mut self.pending_inputs
+    }
+
+    pub fn read_all_outputs(&mut self) -> Vec<i64> {
+        std::mem::take(&mut self.pending_outputs)
+    }
+
+    pub fn read_outputs(&mut self, max: usize) -> Vec<i64> {
+        let max = std::cmp::min(max, self.pending_outputs.len());
+        self.pending_outputs.drain(..max).collect()
+    }
+
+    pub fn status(&self) -> &Status {
+        &self.status
+    }
+
+    pub fn memory(&self) -> &[i64] {
+        &self.intcode
+    }
+
+    pub fn ipointer(&self) -> usize {
+        self.ipointer
+    }
+
+    pub fn relative_base(&self) -> i64 {
+        self.relative_base
+    }
+
+    fn fill_empty(&mut self, idx: &usize) {
+        if self.intcode.len() <= *idx {
+            self.intcode.resize(*idx + 1, 0);
+        }
+    }
+
+    fn intercept_reads(&self, opcode: &Opcode, params: &mut [Parameter]) {
+        let destination = opcode.opcode.destination();
+        for (i, (param, mode)) in params.iter_mut().zip(opcode.parameter_modes).enumerate() {
+            if Some(i) == destination || mode == ParameterType::Immediate {
+                continue;
+            }
+            if let Some(hook) = self.read_hooks.get(&param.index) {
+                param.value = hook(param.value);
+            }
+        }
+    }
+
+    pub fn run(&mut self) {
+        self.run_observed(&mut ());
+    }
+
+    /// Like `run`, but shows every executed instruction to `observer`.
+    pub fn run_observed(&mut self, observer: &mut impl Observer) {
+        self.status = Status::Running;
+        while self.intcode[self.ipointer] != 99 {
+            if self.budget == Some(0) {
+                self.status = Status::BudgetExhausted;
+                return;
+            }
+            let opcode = Opcode::from_int(self.intcode[self.ipointer] as u32);
+            if opcode.opcode == Operation::Input && self.pending_inputs.is_empty() {
+                self.status = Status::WaitingForInput;
+                return;
+            }
+            let step = self.steps();
+            self.counts[opcode.opcode as usize] += 1;
+            if let Some(budget) = self.budget.as_mut() {
+                *budget -= 1;
+            }
+
+            let mut params = opcode.get_params(&self.intcode, &self.ipointer, &self.relative_base);
+            if !self.read_hooks.is_empty() {
+                self.intercept_reads(&opcode, &mut params);
+            }
+            let mut write = None;
+            let mut jump = None;
+            let mut relative_base = None;
+            match opcode.opcode {
+                Operation::Add => {
+                    write = Some((params[2].index, params[0].value + params[1].value))
+                }
+                Operation::Multiply => {
+                    write = Some((params[2].index, params[0].value * params[1].value))
+                }
+                Operation::Input => write = Some((params[0].index, self.pending_inputs.remove(0))),
+                Operation::Output => self.pending_outputs.push(params[0].value),
+                Operation::JumpIfTrue => {
+                    if params[0].value != 0 {
+                        jump = Some(params[1].value as usize);
+                    }
+                }
+                Operation::JumpIfFalse => {
+                    if params[0].value == 0 {
+                        jump = Some(params[1].value as usize);
+                    }
+                }
+                Operation::LessThan => {
+                    write = Some((params[2].index, (params[0].value < params[1].value) as i64))
+                }
+                Operation::Equals => {
+                    write = Some((params[2].index, (params[0].value == params[1].value) as i64))
+                }
+                Operation::AdjustRelativeBase => {
+                    let before = self.relative_base;
+                    self.relative_base += params[0].value;
+                    relative_base = Some((before, self.relative_base));
+                }
+            }
+
+            let write = write.map(|(index, new)| {
+                self.fill_empty(&index);
+                let old = self.intcode[index];
+                let new = self
+                    .write_hooks
+                    .get(&index)
+                    .map_or(new, |hook| hook(old, new));
+                self.intcode[index] = new;
+                Write { index, old, new }
+            });
+            observer.observe(&Event {
+                step,
+                ipointer: self.ipointer,
+                opcode: &opcode,
+                params: &params,
+                write,
+                relative_base,
+                jump,
+            });
+            self.ipointer = jump.unwrap_or(self.ipointer + opcode.variables as usize + 1);
+        }
+        self.status = Status::Halted;
+    }
+}
@@ -0,0 +1,307 @@
+use std::io::{self, Write};
+use std::ops::Range;
+
+use super::ascii::AsciiProgram;
+use super::opcode::{Operation, ParameterType};
+use super::program::{Event, Observer};
+use super::{read_intcode, Program, Status};
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Format {
+    Text,
+    /// One JSON object per line.
+    JsonLines,
+}
+
+/// Logs executed instructions to `sink`, one line each. Filters combine: an
+/// instruction is logged only if it passes every filter that is set.
+pub struct Tracer<W: Write> {
+    sink: W,
+    format: Format,
+    addresses: Option<Range<usize>>,
+    operations: Option<Vec<Operation>>,
+    error: Option<io::Error>,
+}
+
+impl<W: Write> Tracer<W> {
+    pub fn new(sink: W, format: Format) -> Self {
+        Self {
+            sink,
+            format,
+            addresses: None,
+            operations: None,
+            error: None,
+        }
+    }
+
+    /// Only logs instructions whose address falls in `range`.
+    pub fn with_addresses(mut self, range: Range<usize>) -> Self {
+        self.addresses = Some(range);
+        self
+    }
+
+    /// Only logs the given operations.
+    pub fn with_operations(mut self, operations: &[Operation]) -> Self {
+        self.operations = Some(operations.to_vec());
+        self
+    }
+
+    fn wants(&self, event: &Event) -> bool {
+        self.addresses
+            .as_ref()
+            .is_none_or(|r| r.contains(&event.ipointer))
+            && self
+                .operations
+                .as_ref()
+                .is_none_or(|ops| ops.contains(&event.opcode.opcode))
+    }
+
+    /// Flushes the sink and hands it back, along with the first error hit
+    /// while writing, if any.
+    pub fn finish(mut self) -> io::Result<W> {
+        if let Some(e) = self.error.take() {
+            return Err(e);
+        }
+        self.sink.flush()?;
+        Ok(self.sink)
+    }
+}
+
+impl<W: Write> Observer for Tracer<W> {
+    fn observe(&mut self, event: &Event) {
+        if self.error.is_some() || !self.wants(event) {
+            return;
+        }
+        let line = match self.format {
+            Format::Text => text(event),
+            Format::JsonLines => json(event),
+        };
+        if let Err(e) = writeln!(self.sink, "{}", line) {
+            self.error = Some(e);
+        }
+    }
+}
+
+/// `step ip OPERATION operands [-> mem[i]: old => new] [rb: a => b] [jump n]`,
+/// with position operands as `[index]=value`, relative ones as
+/// `rb[index]=value` and immediates as bare values.
+pub fn text(event: &Event) -> String {
+    let mut line = format!(
+        "{:>10} {:>6}  {:<19}",
+        event.step,
+        event.ipointer,
+        format!("{:?}", event.opcode.opcode)
+    );
+    let operands = event
+        .params
+        .iter()
+        .zip(event.opcode.parameter_modes)
+        .map(|(p, mode)| match mode {
+            ParameterType::Position => format!("[{}]={}", p.index, p.value),
+            ParameterType::Immediate => format!("{}", p.value),
+            ParameterType::Relative => format!("rb[{}]={}", p.index, p.value),
+        })
+        .collect::<Vec<_>>();
+    line += &operands.join(" ");
+    if let Some(w) = event.write {
+        line += &format!("  -> mem[{}]: {} => {}", w.index, w.old, w.new);
+    }
+    if let Some((before, after)) = event.relative_base {
+        line += &format!("  rb: {} => {}", before, after);
+    }
+    if let Some(target) = event.jump {
+        line += &format!("  jump {}", target);
+    }
+    line
+}
+
+pub fn json(event: &Event) -> String {
+    let params = event
+        .params
+        .iter()
+        .zip(event.opcode.parameter_modes)
+        .map(|(p, mode)| {
+            format!(
+                r#"{{"mode":"{:?}","index":{},"value":{}}}"#,
+                mode, p.index, p.value
+            )
+        })
+        .collect::<Vec<_>>();
+    let mut line = format!(
+        r#"{{"step":{},"ip":{},"op":"{:?}","params":[{}]"#,
+        event.step,
+        event.ipointer,
+        event.opcode.opcode,
+        params.join(",")
+    );
+    if let Some(w) = event.write {
+        line += &format!(
+            r#","write":{{"index":{},"old":{},"new":{}}}"#,
+            w.index, w.old, w.new
+        );
+    }
+    if let Some((before, after)) = event.relative_base {
+        line += &format!(r#","relative_base":{{"from":{},"to":{}}}"#, before, after);
+    }
+    if let Some(target) = event.jump {
+        line += &format!(r#","jump":{}"#, target);
+    }
+    line + "}"
+}
+
+/// Traces the program in `args[0]` to stdout until it halts or wants more
+/// input. The optional arguments after it pick the format (`text` or
+/// `jsonl`), an address range like `10..20`, operations as a comma
+/// separated list of names or opcodes, and what the program reads:
+/// `input=FILE` for comma separated values, or `ascii=FILE` for lines of
+/// text sent one by one.
+pub fn run(args: &[String]) {
+    let path = args.first().expect("--trace needs an Intcode file");
+    let mut tracer = Tracer::new(io::stdout().lock(), Format::Text);
+    let mut inputs = vec![];
+    let mut lines = String::new();
+    for arg in &args[1..] {
+        tracer = match arg.as_str() {
+            "text" => Tracer {
+                format: Format::Text,
+                ..tracer
+            },
+            "jsonl" => Tracer {
+                format: Format::JsonLines,
+                ..tracer
+            },
+            arg if arg.starts_with("input=") => {
+                inputs.extend(read_intcode(&arg["input=".len()..]));
+                tracer
+            }
+            arg if arg.starts_with("ascii=") => {
+                lines +=
+                    &std::fs::read_to_string(&arg["ascii=".len()..]).expect("File should be there");
+                tracer
+            }
+            range if range.contains("..") => tracer.with_addresses(parse_range(range)),
+            ops => tracer.with_operations(&parse_operations(ops)),
+        };
+    }
+    let mut program = AsciiProgram::new(Program::new(read_intcode(path), inputs));
+    for line in lines.lines() {
+        program
+            .send_line(line)
+            .unwrap_or_else(|e| panic!("Could not send input: {}", e));
+    }
+    program
+        .run_observed(&mut tracer)
+        .unwrap_or_else(|e| panic!("Could not trace the program: {}", e));
+    if let Err(e) = tracer.finish() {
+        panic!("Could not write the trace: {}", e);
+    }
+    if *program.status() == Status::WaitingForInput {
+        eprintln!(
+            "Stopped waiting for input after {} steps",
+            program.program().steps()
+        );
+    }
+}
+
+fn parse_range(text: &str) -> Range<usize> {
+    let (start, end) = text.split_once("..").unwrap();
+    let bound = |n: &str| {
+        n.parse()
+            .unwrap_or_else(|_| panic!("Bad address range {:?}", text))
+    };
+    bound(start)..bound(end)
+}
+
+fn parse_operations(text: &str) -> Vec<Operation> {
+    text.split(',')
+        .map(|op| {
+            Operation::ALL
+                .into_iter()
+                .find(|o| format!("{:?}", o).eq_ignore_ascii_case(op) || o.code().to_string() == op)
+                .unwrap_or_else(|| panic!("Unknown operation {:?}", op))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Echoes each input back, reading it through a relative operand.
+    const ECHO: [i64; 9] = [109, 5, 203, 25, 4, 30, 1105, 1, 2];
+
+    fn trace(tracer: Tracer<Vec<u8>>, inputs: Vec<i64>) -> Vec<String> {
+        let mut tracer = tracer;
+        Program::new(ECHO.to_vec(), inputs).run_observed(&mut tracer);
+        let sink = tracer.finish().unwrap();
+        String::from_utf8(sink)
+            .unwrap()
+            .lines()
+            .map(str::to_string)
+            .collect()
+    }
+
+    #[test]
+    fn text() {
+        let lines = trace(Tracer::new(vec![], Format::Text), vec![7]);
+        assert_eq!(
+            lines,
+            vec![
+                "         0      0  AdjustRelativeBase 5  rb: 0 => 5",
+                "         1      2  Input              rb[30]=0  -> mem[30]: 0 => 7",
+                "         2      4  Output             [30]=7",
+                "         3      6  JumpIfTrue         1 2  jump 2",
+            ]
+        );
+    }
+
+    #[test]
+    fn json_lines() {
+        let lines = trace(Tracer::new(vec![], Format::JsonLines), vec![7]);
+        assert_eq!(
+            lines,
+            vec![
+                r#"{"step":0,"ip":0,"op":"AdjustRelativeBase","params":[{"mode":"Immediate","index":1,"value":5}],"relative_base":{"from":0,"to":5}}"#,
+                r#"{"step":1,"ip":2,"op":"Input","params":[{"mode":"Relative","index":30,"value":0}],"write":{"index":30,"old":0,"new":7}}"#,
+                r#"{"step":2,"ip":4,"op":"Output","params":[{"mode":"Position","index":30,"value":7}]}"#,
+                r#"{"step":3,"ip":6,"op":"JumpIfTrue","params":[{"mode":"Immediate","index":7,"value":1},{"mode":"Immediate","index":8,"value":2}],"jump":2}"#,
+            ]
+        );
+    }
+
+    #[test]
+    fn filters() {
+        let addresses = Tracer::new(vec![], Format::Text).with_addresses(2..6);
+        let steps = trace(addresses, vec![7, 8])
+            .iter()
+            .map(|l| l.split_whitespace().next().unwrap().to_string())
+            .collect::<Vec<_>>();
+        assert_eq!(steps, vec!["1", "2", "4", "5"]);
+
+        let operations = Tracer::new(vec![], Format::Text)
+            .with_operations(&[Operation::Output, Operation::AdjustRelativeBase]);
+        assert_eq!(trace(operations, vec![7, 8]).len(), 3);
+
+        let both = Tracer::new(vec![], Format::Text)
+            .with_addresses(2..6)
+            .with_operations(&[Operation::Output]);
+        assert_eq!(
+            trace(both, vec![7, 8]),
+            vec![
+                "         2      4  Output             [30]=7",
+                "         5      4  Output             [30]=8",
+            ]
+        );
+    }
+
+    #[test]
+    fn ascii_program() {
+        let mut program = AsciiProgram::new(Program::new(ECHO.to_vec(), vec![]));
+        program.send_line("hi").unwrap();
+        let mut tracer = Tracer::new(vec![], Format::Text).with_operations(&[Operation::Input]);
+        program.run_observed(&mut tracer).unwrap();
+        assert_eq!(program.read_text(), Ok("hi\n".to_string()));
+        let sink = tracer.finish().unwrap();
+        assert_eq!(String::from_utf8(sink).unwrap().lines().count(), 3);
+    }
+}
@@ -1,7 +1,28 @@
 use days::*;
 mod days;
+mod intcode;
 
 fn main() {
+    let args = std::env::args().collect::<Vec<_>>();
+    match args.get(1).map(String::as_str) {
+        Some("--bench") => return intcode::bench::run(),
+        Some("--cfg") => {
+            let path = args.get(2).expect("--cfg needs an Intcode file");
+            let memory = intcode::read_intcode(path);
+            return print!("{}", intcode::cfg::Cfg::recover(&memory).to_dot());
+        }
+        Some("--coverage") => {
+            let path = args.get(2).expect("--coverage needs an Intcode file");
+            let inputs = args.get(3).map_or(vec![], |p| intcode::read_intcode(p));
+            return intcode::coverage::run(path, inputs);
+        }
+        Some("--trace") => return intcode::trace::run(&args[2..]),
+        Some("--play-day13") => return day13::play(),
+        Some("--profile-day13") => return day13::profile(),
+        Some("--watch-day17") => return day17::watch(),
+        Some("--play-day25") => return day25::play(),
+        _ => {}
+    }
     day01::run();
     day02::run();
     day03::run();